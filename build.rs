@@ -0,0 +1,30 @@
+use std::env;
+use std::process::Command;
+
+// Detect whether the compiler has the standard library's integer square roots, which the
+// `core-isqrt` feature delegates to, and the AVX-512 intrinsics of the x86-64 slice kernels. They
+// were stabilised in Rust 1.84 and 1.89.
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(has_core_isqrt)");
+    println!("cargo:rustc-check-cfg=cfg(has_avx512)");
+    let minor = rustc_minor_version();
+    if minor.is_some_and(|minor| minor >= 84) {
+        println!("cargo:rustc-cfg=has_core_isqrt");
+    }
+    if minor.is_some_and(|minor| minor >= 89) {
+        println!("cargo:rustc-cfg=has_avx512");
+    }
+}
+
+/// The minor version of the compiler, from the likes of `rustc 1.84.0 (9fc6b4312 2025-01-07)`.
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = String::from_utf8(output.stdout).ok()?;
+    let mut parts = version.split_whitespace().nth(1)?.split('.');
+    if parts.next()? != "1" {
+        return None;
+    }
+    parts.next()?.parse().ok()
+}
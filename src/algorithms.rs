@@ -0,0 +1,259 @@
+//! The individual algorithms behind [`IntegerSquareRoot::integer_sqrt`], to call one directly,
+//! benchmark them against each other, or build a different choice between them.
+//!
+//! [`integer_sqrt`] picks one of these by the width of the type and the features of the build.
+//! Each gives the same root, and they differ only in speed, code size and what hardware they
+//! use.
+//!
+//! ```
+//! extern crate integer_sqrt;
+//! use integer_sqrt::algorithms;
+//!
+//! # fn main() {
+//! let n = 1_000_000_007u64;
+//! assert_eq!(algorithms::bitwise(n), 31_622);
+//! assert_eq!(algorithms::newton(n), 31_622);
+//! assert_eq!(algorithms::karatsuba_u64(n), 31_622);
+//! # }
+//! ```
+//!
+//! [`IntegerSquareRoot::integer_sqrt`]: ../trait.IntegerSquareRoot.html#method.integer_sqrt
+//! [`integer_sqrt`]: ../trait.IntegerSquareRoot.html#method.integer_sqrt
+
+use primitive::Primitive;
+use {binary_search, bitwise, cordic, halley, karatsuba, newton, table};
+
+/// Find the integer square root a bit at a time, with the classic binary method, which needs no
+/// multiplications, divisions or tables. This is what the `algo-bitwise` feature uses.
+///
+/// # Panics
+///
+/// For negative numbers (`i` family) this function will panic on negative input
+pub fn bitwise<T: Primitive>(n: T) -> T {
+    assert_non_negative(n);
+    bitwise::bitwise_sqrt(n)
+}
+
+/// Find the integer square root with the binary method, running one round for every pair of bits
+/// in `T` whatever the number, so that the worst case execution time can be bounded. This is what
+/// the `wcet` feature uses.
+///
+/// # Panics
+///
+/// For negative numbers (`i` family) this function will panic on negative input
+pub fn bitwise_fixed<T: Primitive>(n: T) -> T {
+    assert_non_negative(n);
+    bitwise::bitwise_sqrt_fixed(n)
+}
+
+/// Find the integer square root by CORDIC in hyperbolic vectoring mode, with shifts and additions
+/// in a 32 bit fixed point pipeline, as [`cordic_fixed`] describes. Roots of up to 22 bits come
+/// out of the pipeline within one and are corrected by squaring, so for `u32` and smaller types
+/// the pipeline does all the work. Larger roots, of most `u64`s and every `u128`, are finished
+/// by Newton's method from the pipeline's estimate. This is what the `algo-cordic` feature
+/// uses.
+///
+/// # Panics
+///
+/// For negative numbers (`i` family) this function will panic on negative input
+///
+/// [`cordic_fixed`]: ./fn.cordic_fixed.html
+pub fn cordic<T: Primitive>(n: T) -> T {
+    assert_non_negative(n);
+    cordic::cordic_sqrt(n)
+}
+
+/// Find the square root of a fixed point number from 0.25 to 1, with 30 fractional bits, by
+/// CORDIC, as a fixed point number with 30 fractional bits.
+///
+/// This is the raw result of the pipeline behind [`cordic`], to check a hardware implementation
+/// against bit for bit. `v` and `v - 1/4` are rotated by `atanh(2^-i)`, for `i` from 1 to 30 with
+/// rounds 4 and 13 taken twice, using arithmetic shifts that round down, and the gain is then
+/// divided out by multiplying by `0x4d47a1c8` and dropping the low 30 bits. The result is within
+/// a few dozen units in the last place of the square root, unrounded.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::algorithms;
+///
+/// # fn main() {
+/// // The root of 0.5 is 0.7071067...
+/// let root = algorithms::cordic_fixed(1 << 29);
+/// assert_eq!(root >> 10, 741_455);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If `v` is below `2^28` or above `2^30`.
+///
+/// [`cordic`]: ./fn.cordic.html
+pub fn cordic_fixed(v: u32) -> u32 {
+    assert!(
+        (1 << 28..=1 << 30).contains(&v),
+        "cannot calculate square root outside of 0.25 to 1"
+    );
+    cordic::cordic_fixed(v.into()) as u32
+}
+
+/// Find the integer square root by binary search on the root, which cannot overflow. This is
+/// the slowest method, but the simplest to check, for safety-critical code. This is what the
+/// `algo-binary-search` feature uses.
+///
+/// # Panics
+///
+/// For negative numbers (`i` family) this function will panic on negative input
+pub fn binary_search<T: Primitive>(n: T) -> T {
+    assert_non_negative(n);
+    binary_search::binary_search_sqrt(n)
+}
+
+/// Find the integer square root by Halley's method, from a power of two no smaller than the
+/// root. It converges cubically, so it takes fewer steps than [`newton`], though each costs a
+/// multiplication and two divisions. This is what the `algo-halley` feature uses.
+///
+/// # Panics
+///
+/// For negative numbers (`i` family) this function will panic on negative input
+///
+/// [`newton`]: ./fn.newton.html
+pub fn halley<T: Primitive>(n: T) -> T {
+    assert_non_negative(n);
+    halley::halley_sqrt(n)
+}
+
+/// Find the integer square root by Newton's method, from a power of two no smaller than the
+/// root. This is what the `algo-newton` feature uses, and the default for the widest types.
+///
+/// # Panics
+///
+/// For negative numbers (`i` family) this function will panic on negative input
+pub fn newton<T: Primitive>(n: T) -> T {
+    assert_non_negative(n);
+    newton::newton_sqrt(n)
+}
+
+/// Find the integer square root by Newton's method, starting from the root of the top byte of the
+/// number, looked up in a table. This is the default for the 16 and 32 bit types without a float
+/// square root.
+///
+/// # Panics
+///
+/// For negative numbers (`i` family) this function will panic on negative input
+pub fn table_seeded<T: Primitive>(n: T) -> T {
+    assert_non_negative(n);
+    table::table_seeded_sqrt(n)
+}
+
+/// Look up the integer square root of a `u8` in a table. This is the default for the 8 bit types.
+pub fn lookup_u8(n: u8) -> u8 {
+    table::table_sqrt(n)
+}
+
+/// Find the integer square root of a `u64` from the 32 bit root of its top half, by Zimmermann's
+/// Karatsuba square root. This is the default for 64 bit types on 16 and 32 bit targets.
+pub fn karatsuba_u64(n: u64) -> u64 {
+    karatsuba::karatsuba_sqrt_u64(n)
+}
+
+/// Find the integer square root of a `u128` from the 64 bit root of its top half, by Zimmermann's
+/// Karatsuba square root. This is the default for the 128 bit types.
+pub fn karatsuba_u128(n: u128) -> u128 {
+    karatsuba::karatsuba_sqrt_u128(n)
+}
+
+/// Find the integer square root of a `u32` from its single precision float square root. This is
+/// the default for the 16 and 32 bit types with the `std`, `libm`, `sse2` or `vsqrt` features.
+#[cfg(any(
+    feature = "std",
+    feature = "libm",
+    all(feature = "sse2", target_feature = "sse2"),
+    all(feature = "vsqrt", target_arch = "arm", target_abi = "eabihf")
+))]
+pub fn float_u32(n: u32) -> u32 {
+    ::float::float_sqrt_u32(n)
+}
+
+/// Find the integer square root of a `u64` from its double precision float square root. This is
+/// the default for the 64 bit types with the `std`, `libm`, `sse2` or `vsqrt-f64` features.
+#[cfg(any(
+    feature = "std",
+    feature = "libm",
+    all(feature = "sse2", target_feature = "sse2"),
+    all(feature = "vsqrt-f64", target_arch = "arm", target_abi = "eabihf")
+))]
+pub fn float_u64(n: u64) -> u64 {
+    ::float::float_sqrt(n)
+}
+
+fn assert_non_negative<T: Primitive>(n: T) {
+    assert!(
+        n >= T::zero(),
+        "cannot calculate square root of negative number"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use IntegerSquareRoot;
+
+    #[test]
+    fn agree() {
+        for n in (0..=u32::from(u16::MAX)).chain(u32::MAX - u32::from(u16::MAX)..=u32::MAX) {
+            let root = n.integer_sqrt();
+            assert_eq!(bitwise(n), root, "in {}", n);
+            assert_eq!(bitwise_fixed(n), root, "in {}", n);
+            assert_eq!(newton(n), root, "in {}", n);
+            assert_eq!(cordic(n), root, "in {}", n);
+            assert_eq!(binary_search(n), root, "in {}", n);
+            assert_eq!(halley(n), root, "in {}", n);
+            assert_eq!(table_seeded(n), root, "in {}", n);
+            assert_eq!(karatsuba_u64(n.into()), root.into(), "in {}", n);
+            #[cfg(any(
+                feature = "std",
+                feature = "libm",
+                all(feature = "sse2", target_feature = "sse2"),
+                all(feature = "vsqrt", target_arch = "arm", target_abi = "eabihf")
+            ))]
+            assert_eq!(float_u32(n), root, "in {}", n);
+        }
+        for n in 0..=u8::MAX {
+            assert_eq!(lookup_u8(n), n.integer_sqrt(), "in {}", n);
+        }
+        for shift in 0..64 {
+            for n in [1u64 << shift, (1 << shift) - 1, u64::MAX >> shift] {
+                let root = n.integer_sqrt();
+                assert_eq!(karatsuba_u64(n), root, "in {}", n);
+                assert_eq!(bitwise(n), root, "in {}", n);
+                assert_eq!(cordic(n), root, "in {}", n);
+                assert_eq!(binary_search(n), root, "in {}", n);
+                assert_eq!(halley(n), root, "in {}", n);
+                #[cfg(any(
+                    feature = "std",
+                    feature = "libm",
+                    all(feature = "sse2", target_feature = "sse2"),
+                    all(feature = "vsqrt-f64", target_arch = "arm", target_abi = "eabihf")
+                ))]
+                assert_eq!(float_u64(n), root, "in {}", n);
+                let wide = u128::from(n) * u128::from(u64::MAX) + u128::from(n);
+                assert_eq!(karatsuba_u128(wide), newton(wide), "in {}", wide);
+            }
+        }
+        assert_eq!(bitwise(i64::MAX), newton(i64::MAX));
+        assert_eq!(binary_search(i128::MAX), newton(i128::MAX));
+        assert_eq!(halley(u128::MAX), newton(u128::MAX));
+    }
+
+    #[test]
+    #[should_panic]
+    fn cordic_out_of_range() {
+        cordic_fixed((1 << 28) - 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative() {
+        table_seeded(-1i32);
+    }
+}
@@ -0,0 +1,107 @@
+use {IntegerSqrtAssign, IntegerSquareRoot};
+
+/// A trait implementing elementwise integer square root for fixed-size arrays.
+///
+/// This is for small vectors of numbers, such as colour channels or the lanes of an accumulator,
+/// that are rooted together. The arrays also implement [`IntegerSqrtAssign`], to root them in
+/// place.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::{ArraySquareRoot, IntegerSqrtAssign};
+///
+/// # fn main() {
+/// assert_eq!([16u8, 100, 255].integer_sqrt(), [4, 10, 15]);
+/// assert_eq!([4i32, -4].integer_sqrt_checked(), None);
+///
+/// let mut lanes = [1u64, 2, 3, 4];
+/// lanes.integer_sqrt_assign();
+/// assert_eq!(lanes, [1, 1, 1, 2]);
+/// # }
+/// ```
+///
+/// [`IntegerSqrtAssign`]: ./trait.IntegerSqrtAssign.html
+pub trait ArraySquareRoot {
+    /// Find the integer square root of every element.
+    ///
+    /// # Panics
+    ///
+    /// If any element is negative (`i` family)
+    fn integer_sqrt(&self) -> Self
+    where
+        Self: Sized,
+    {
+        self.integer_sqrt_checked()
+            .expect("cannot calculate square root of negative number")
+    }
+
+    /// Find the integer square root of every element, returning `None` if any of them is
+    /// negative (this can never happen for unsigned types).
+    fn integer_sqrt_checked(&self) -> Option<Self>
+    where
+        Self: Sized;
+}
+
+impl<T: IntegerSquareRoot + Copy, const N: usize> ArraySquareRoot for [T; N] {
+    fn integer_sqrt_checked(&self) -> Option<Self> {
+        let mut roots = *self;
+        for root in roots.iter_mut() {
+            *root = root.integer_sqrt_checked()?;
+        }
+        Some(roots)
+    }
+}
+
+impl<T: IntegerSquareRoot + Copy, const N: usize> IntegerSqrtAssign for [T; N] {
+    fn integer_sqrt_assign(&mut self) {
+        *self = ArraySquareRoot::integer_sqrt(self);
+    }
+
+    /// Replace every element with its integer square root, returning `false` and leaving the
+    /// whole array unchanged if any element is negative.
+    fn integer_sqrt_assign_checked(&mut self) -> bool {
+        match self.integer_sqrt_checked() {
+            Some(roots) => {
+                *self = roots;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArraySquareRoot;
+    use IntegerSqrtAssign;
+    use IntegerSquareRoot;
+
+    #[test]
+    fn elementwise() {
+        let values = [0u32, 1, 15, 16, 17, u32::MAX];
+        let roots = ArraySquareRoot::integer_sqrt(&values);
+        for (value, root) in values.iter().zip(roots.iter()) {
+            assert_eq!(value.integer_sqrt(), *root, "in {}", value);
+        }
+        assert_eq!(ArraySquareRoot::integer_sqrt(&[0u8; 0]), []);
+        assert_eq!([9i8, 10, -1].integer_sqrt_checked(), None);
+    }
+
+    #[test]
+    fn assign() {
+        let mut values = [81i64, 82, 99];
+        values.integer_sqrt_assign();
+        assert_eq!(values, [9, 9, 9]);
+        let mut values = [81i64, -1];
+        assert!(!values.integer_sqrt_assign_checked());
+        assert_eq!(values, [81, -1]);
+        let values: &mut dyn IntegerSqrtAssign = &mut [4u16, 9];
+        assert!(values.integer_sqrt_assign_checked());
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative() {
+        ArraySquareRoot::integer_sqrt(&[1i32, -1]);
+    }
+}
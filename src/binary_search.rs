@@ -0,0 +1,63 @@
+use primitive::Primitive;
+
+/// Find the integer square root of non-negative `n` by binary search on the root.
+///
+/// The root is kept between `low` and `high`, and the range is halved until it holds a single
+/// number. Nothing in it can overflow: the midpoint is found from the difference of the bounds
+/// rather than their sum, and `mid * mid <= n` is checked as `mid <= n / mid`. It takes one
+/// division per bit of the root, so it is slower than the other methods, but it is the simplest
+/// to check, for the `algo-binary-search` feature.
+pub(crate) fn binary_search_sqrt<T: Primitive>(n: T) -> T {
+    // n < 2^bits, so the root is below 2^ceil(bits / 2), which is at most half the width of T
+    let bits = T::zero().leading_zeros() - n.leading_zeros();
+    let mut low = T::zero();
+    let mut high = T::one().unsigned_shl(bits.div_ceil(2)) - T::one();
+    while low < high {
+        // Rounding up keeps mid above low, so the range always shrinks and mid is never 0
+        let mid = high - (high - low) / (T::one() + T::one());
+        if mid <= n / mid {
+            low = mid;
+        } else {
+            high = mid - T::one();
+        }
+    }
+    low
+}
+
+#[cfg(test)]
+mod tests {
+    use super::binary_search_sqrt;
+    use newton::newton_sqrt;
+
+    #[test]
+    fn binary_search() {
+        for n in 0..=u16::MAX {
+            assert_eq!(binary_search_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for n in 0..=i16::MAX {
+            assert_eq!(binary_search_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for n in 0..=u8::MAX {
+            assert_eq!(binary_search_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for n in 0..=i8::MAX {
+            assert_eq!(binary_search_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for shift in 0..128 {
+            for n in [1u128 << shift, (1 << shift) - 1, u128::MAX >> shift] {
+                assert_eq!(binary_search_sqrt(n), newton_sqrt(n), "in {}", n);
+                let n = (n >> 1) as i128;
+                assert_eq!(binary_search_sqrt(n), newton_sqrt(n), "in {}", n);
+            }
+        }
+        for root in (0..1u64 << 12).map(|i| u64::MAX - (i << 40)) {
+            let square = u128::from(root) * u128::from(root);
+            for n in [square - 1, square, square + 2 * u128::from(root)] {
+                assert_eq!(
+                    binary_search_sqrt(n),
+                    u128::from(root) - (n < square) as u128
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use primitive::Primitive;
+
+/// Find the integer square root of non-negative `n` a bit at a time, with the classic binary
+/// method.
+///
+/// Each step only shifts, adds and compares, in the width of `T`, so this needs no multiplier,
+/// divider, floating point unit or table. It is the smallest way to take a root, for the
+/// `algo-bitwise` feature, and the fastest on 16 bit targets such as AVR and MSP430, where
+/// dividing takes hundreds of cycles.
+#[inline]
+pub(crate) fn bitwise_sqrt<T: Primitive>(n: T) -> T {
+    bitwise_sqrt_rem(n).0
+}
+
+/// Find the integer square root of non-negative `n` with [`bitwise_sqrt`], along with the
+/// remainder `n - root * root`, which the method leaves behind without squaring the root.
+///
+/// So the remainder, and the ceiling, rounded and exact roots found from it, need no multiplier
+/// either, for cores without one such as the Cortex-M0 and RV32E.
+pub(crate) fn bitwise_sqrt_rem<T: Primitive>(n: T) -> (T, T) {
+    // Start from the highest power of 4 that is no larger than n. AVR counts the leading zeros a
+    // bit at a time, so there it is smaller to step down to it.
+    #[cfg(target_arch = "avr")]
+    let mut bit = {
+        let mut bit = T::one().unsigned_shl((T::zero().count_zeros() - 2) & !1);
+        while bit > n {
+            bit = bit.unsigned_shr(2);
+        }
+        bit
+    };
+    // For 0 this starts from 1, which leaves the root and remainder at 0
+    #[cfg(not(target_arch = "avr"))]
+    let mut bit = {
+        let bits = T::zero().leading_zeros() - n.leading_zeros();
+        T::one().unsigned_shl(bits.saturating_sub(1) & !1)
+    };
+    let mut rem = n;
+    let mut root = T::zero();
+    while bit != T::zero() {
+        // `root` only has bits above `bit`, so the sum never overflows
+        let trial = root + bit;
+        if rem >= trial {
+            rem = rem - trial;
+            root = root.unsigned_shr(1) + bit;
+        } else {
+            root = root.unsigned_shr(1);
+        }
+        bit = bit.unsigned_shr(2);
+    }
+    (root, rem)
+}
+
+/// Find the integer square root of non-negative `n` with the binary method, always running one
+/// round for every pair of bits in `T`.
+///
+/// Unlike [`bitwise_sqrt`], this does not skip the leading zeros of `n`, so the number of rounds
+/// depends only on the width of `T`. Each round is a comparison, a subtraction and three shifts,
+/// with a single branch, which bounds its worst case execution time for the `wcet` feature.
+#[inline]
+pub(crate) fn bitwise_sqrt_fixed<T: Primitive>(n: T) -> T {
+    bitwise_sqrt_fixed_rem(n).0
+}
+
+/// Find the integer square root of non-negative `n` with [`bitwise_sqrt_fixed`], along with the
+/// remainder `n - root * root`.
+pub(crate) fn bitwise_sqrt_fixed_rem<T: Primitive>(n: T) -> (T, T) {
+    let bits = T::zero().count_zeros();
+    // The top pair of bits of a signed type is never more than 01
+    let mut bit = T::one().unsigned_shl((bits - 2) & !1);
+    let mut rem = n;
+    let mut root = T::zero();
+    for _ in 0..bits / 2 {
+        let trial = root + bit;
+        if rem >= trial {
+            rem = rem - trial;
+            root = root.unsigned_shr(1) + bit;
+        } else {
+            root = root.unsigned_shr(1);
+        }
+        bit = bit.unsigned_shr(2);
+    }
+    (root, rem)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bitwise_sqrt, bitwise_sqrt_fixed, bitwise_sqrt_fixed_rem, bitwise_sqrt_rem};
+    use newton::newton_sqrt;
+
+    #[test]
+    fn bitwise() {
+        for n in 0..=u16::MAX {
+            assert_eq!(bitwise_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for n in 0..=i16::MAX {
+            assert_eq!(bitwise_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for shift in 0..128 {
+            for n in [1u128 << shift, (1 << shift) - 1, u128::MAX >> shift] {
+                assert_eq!(bitwise_sqrt(n), newton_sqrt(n), "in {}", n);
+                let n = (n >> 1) as i128;
+                assert_eq!(bitwise_sqrt(n), newton_sqrt(n), "in {}", n);
+            }
+        }
+        for root in (0..1u64 << 12).map(|i| u64::MAX - (i << 40)) {
+            let square = u128::from(root) * u128::from(root);
+            for n in [square - 1, square, square + 2 * u128::from(root)] {
+                assert_eq!(bitwise_sqrt(n), u128::from(root) - (n < square) as u128);
+            }
+        }
+    }
+
+    #[test]
+    fn bitwise_fixed() {
+        for n in 0..=u16::MAX {
+            assert_eq!(bitwise_sqrt_fixed(n), bitwise_sqrt(n), "in {}", n);
+        }
+        for n in 0..=i16::MAX {
+            assert_eq!(bitwise_sqrt_fixed(n), bitwise_sqrt(n), "in {}", n);
+        }
+        for n in 0..=u8::MAX {
+            assert_eq!(bitwise_sqrt_fixed(n), bitwise_sqrt(n), "in {}", n);
+        }
+        for shift in 0..128 {
+            for n in [1u128 << shift, (1 << shift) - 1, u128::MAX >> shift] {
+                assert_eq!(bitwise_sqrt_fixed(n), bitwise_sqrt(n), "in {}", n);
+                let n = (n >> 1) as i128;
+                assert_eq!(bitwise_sqrt_fixed(n), bitwise_sqrt(n), "in {}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn bitwise_rem() {
+        for n in 0..=u16::MAX {
+            let root = bitwise_sqrt(n);
+            let rem = n - root * root;
+            assert_eq!(bitwise_sqrt_rem(n), (root, rem), "in {}", n);
+            assert_eq!(bitwise_sqrt_fixed_rem(n), (root, rem), "in {}", n);
+        }
+        for shift in 0..128 {
+            for n in [1u128 << shift, (1 << shift) - 1, u128::MAX >> shift] {
+                let root = bitwise_sqrt(n);
+                assert_eq!(bitwise_sqrt_rem(n), (root, n - root * root), "in {}", n);
+                assert_eq!(
+                    bitwise_sqrt_fixed_rem(n),
+                    (root, n - root * root),
+                    "in {}",
+                    n
+                );
+                let n = (n >> 1) as i128;
+                let root = bitwise_sqrt(n);
+                assert_eq!(bitwise_sqrt_rem(n), (root, n - root * root), "in {}", n);
+                assert_eq!(
+                    bitwise_sqrt_fixed_rem(n),
+                    (root, n - root * root),
+                    "in {}",
+                    n
+                );
+            }
+        }
+    }
+}
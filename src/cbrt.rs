@@ -0,0 +1,198 @@
+use nth_root::odd_root_of_negative;
+use primitive::Primitive;
+
+/// A trait implementing integer cube root.
+pub trait IntegerCubeRoot {
+    /// Find the integer cube root.
+    ///
+    /// This is the digit-by-digit (base 2) method, the cube root analogue of the classic binary
+    /// algorithm for [integer square roots][`IntegerSquareRoot`].
+    ///
+    /// Negative numbers (`i` family) have a negative cube root, which is rounded towards zero, so
+    /// `(-27).integer_cbrt()` and `(-28).integer_cbrt()` are both `-3`.
+    ///
+    /// [`IntegerSquareRoot`]: ./trait.IntegerSquareRoot.html
+    fn integer_cbrt(&self) -> Self
+    where
+        Self: Sized,
+    {
+        self.integer_cbrt_checked()
+            .expect("cannot calculate cube root")
+    }
+
+    /// Find the integer cube root.
+    ///
+    /// Every integer has a cube root, so this never returns `None` for the primitive types. It is
+    /// provided for symmetry with the checked methods of the other root traits.
+    fn integer_cbrt_checked(&self) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Returns `true` if the number is a perfect cube, i.e. `r * r * r` for some integer `r`
+    /// (which may be negative for the `i` family).
+    fn is_perfect_cube(&self) -> bool
+    where
+        Self: Sized;
+}
+
+impl<T: Primitive> IntegerCubeRoot for T {
+    fn integer_cbrt_checked(&self) -> Option<Self> {
+        if *self < T::zero() {
+            Some(odd_root_of_negative(*self, 3, cbrt_non_negative))
+        } else {
+            Some(cbrt_non_negative(*self))
+        }
+    }
+
+    fn is_perfect_cube(&self) -> bool {
+        // Cubes are congruent to 0 or +-1 both mod 9 and mod 7, which rules out about 6 in 7
+        // numbers before we need to compute a root.
+        const RESIDUES_MOD_9: u32 = 0b1_0000_0011;
+        const RESIDUES_MOD_7: u32 = 0b100_0011;
+        let residue = |m: u32| {
+            let m = T::from(m).unwrap();
+            let r = *self % m;
+            let r = if r < T::zero() { r + m } else { r };
+            r.to_u32().unwrap()
+        };
+        if RESIDUES_MOD_9 & (1 << residue(9)) == 0 || RESIDUES_MOD_7 & (1 << residue(7)) == 0 {
+            return false;
+        }
+
+        // The root is rounded towards zero, so its cube can never overflow.
+        let root = self.integer_cbrt();
+        root * root * root == *self
+    }
+}
+
+/// The digit-by-digit cube root of a non-negative number.
+pub(crate) fn cbrt_non_negative<T: Primitive>(n: T) -> T {
+    if n == T::zero() {
+        return T::zero();
+    }
+
+    // Compute shift, the largest multiple of 3 <= the index of the highest set bit
+    let high_bit: u32 = T::zero().leading_zeros() - n.leading_zeros() - 1;
+    let mut shift = high_bit - high_bit % 3;
+
+    // Each round brings down the next 3 bits of n and decides the next bit of the result.
+    // Comparing against `n >> shift` rather than `b << shift` keeps every intermediate
+    // value in range, even for the widest types.
+    let three = T::one() + T::one() + T::one();
+    let mut n = n;
+    let mut result = T::zero();
+    loop {
+        result = result + result;
+        let b = three * result * (result + T::one()) + T::one();
+        if n.unsigned_shr(shift) >= b {
+            n = n - b.unsigned_shl(shift);
+            result = result + T::one();
+        }
+        if shift == 0 {
+            break;
+        }
+        shift -= 3;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntegerCubeRoot;
+
+    macro_rules! gen_tests {
+        ($($type:ty => $fn_name:ident),*) => {
+            $(
+                #[test]
+                fn $fn_name() {
+                    let is_cbrt_of = |root: $type, n: $type| {
+                        let cube = |x: $type| x.checked_mul(x).and_then(|sq| sq.checked_mul(x));
+                        cube(root).map_or(false, |c| c <= n)
+                            && cube(root + 1).map_or(true, |c| c > n)
+                    };
+                    let tests: [($type, $type); 9] = [
+                        (0, 0),
+                        (1, 1),
+                        (2, 1),
+                        (7, 1),
+                        (8, 2),
+                        (26, 2),
+                        (27, 3),
+                        (63, 3),
+                        (64, 4),
+                    ];
+                    for &(in_, out) in tests.iter() {
+                        assert_eq!(in_.integer_cbrt(), out, "in {}", in_);
+                    }
+                    for &in_ in [<$type>::MAX, <$type>::MAX - 1, <$type>::MAX / 3].iter() {
+                        assert!(is_cbrt_of(in_.integer_cbrt(), in_), "in {}", in_);
+                    }
+                }
+            )*
+        };
+    }
+
+    gen_tests! {
+        i8 => i8_test,
+        u8 => u8_test,
+        i16 => i16_test,
+        u16 => u16_test,
+        i32 => i32_test,
+        u32 => u32_test,
+        i64 => i64_test,
+        u64 => u64_test,
+        i128 => i128_test,
+        u128 => u128_test,
+        isize => isize_test,
+        usize => usize_test
+    }
+
+    #[test]
+    fn exhaustive_u16() {
+        for n in 0..=u16::MAX {
+            let r = u32::from(n.integer_cbrt());
+            let n = u32::from(n);
+            assert!(
+                r * r * r <= n && (r + 1) * (r + 1) * (r + 1) > n,
+                "in {}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn perfect_cube() {
+        for n in i16::MIN..=i16::MAX {
+            let r = n.integer_cbrt();
+            assert_eq!(n.is_perfect_cube(), r * r * r == n, "in {}", n);
+        }
+        for n in 0..=u16::MAX {
+            let r = n.integer_cbrt();
+            assert_eq!(n.is_perfect_cube(), r * r * r == n, "in {}", n);
+        }
+        assert!(!i8::MIN.is_perfect_cube());
+        assert!((-125i8).is_perfect_cube());
+        assert!(i64::MIN.is_perfect_cube());
+        assert!(!u64::MAX.is_perfect_cube());
+        assert!((2_642_245u64 * 2_642_245 * 2_642_245).is_perfect_cube());
+        assert!(((1u128 << 42) - 1).pow(3).is_perfect_cube());
+        assert!(!((1u128 << 42) - 1).pow(3).wrapping_add(1).is_perfect_cube());
+    }
+
+    #[test]
+    fn negative() {
+        assert_eq!((-1i32).integer_cbrt(), -1);
+        assert_eq!((-27i32).integer_cbrt(), -3);
+        assert_eq!((-28i32).integer_cbrt(), -3);
+        assert_eq!(i8::MIN.integer_cbrt(), -5);
+        assert_eq!(i64::MIN.integer_cbrt(), -2_097_152);
+        for n in i16::MIN..0 {
+            assert_eq!(
+                n.integer_cbrt(),
+                -(-i32::from(n)).integer_cbrt() as i16,
+                "in {}",
+                n
+            );
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use newton::{first_guess, newton_sqrt_from};
+use primitive::Primitive;
+
+/// The fractional bits of the fixed point numbers in the CORDIC pipeline, which holds numbers
+/// from 0.25 to 1 in the low 31 bits of a 64 bit word.
+const FRAC: u32 = 30;
+
+/// The reciprocal of the gain of the hyperbolic rotations, `1 / 0.8281593609602...`, with `FRAC`
+/// fractional bits.
+const INV_GAIN: u64 = 0x4d47_a1c8;
+
+/// Find the square root of the fixed point number `v / 2^30`, which must be from 0.25 to 1, as a
+/// fixed point number with 30 fractional bits, by CORDIC in hyperbolic vectoring mode.
+///
+/// Vectoring rotates `(v + 1/4, v - 1/4)` onto the x axis, which leaves `x` as the gain times
+/// `sqrt(x^2 - y^2) = sqrt(v)`. The rotations are by `atanh(2^-i)` for `i` from 1 to 30, taking
+/// rounds 4 and 13 twice so that they converge, and each is a pair of arithmetic shifts and
+/// additions. The gain is divided out with a single multiplication at the end.
+pub(crate) fn cordic_fixed(v: u64) -> u64 {
+    let quarter = 1 << (FRAC - 2);
+    let (mut x, mut y) = (v as i64 + quarter, v as i64 - quarter);
+    let mut repeat = 4;
+    for i in 1..=FRAC {
+        let rounds = if i == repeat {
+            repeat = 3 * repeat + 1;
+            2
+        } else {
+            1
+        };
+        for _ in 0..rounds {
+            let (dx, dy) = (y >> i, x >> i);
+            if y < 0 {
+                x += dx;
+                y += dy;
+            } else {
+                x -= dx;
+                y -= dy;
+            }
+        }
+    }
+    (x as u64 * INV_GAIN) >> FRAC
+}
+
+/// Find the integer square root of non-negative `n` by CORDIC.
+///
+/// `n` is shifted by an even number of bits to a fixed point number from 0.25 to 1, whose root
+/// [`cordic_fixed`] finds to about 25 bits and is shifted back by half as many. When that leaves
+/// at least 8 bits below the point, the root is within one and is fixed up by squaring it, and
+/// otherwise the root is finished by Newton's method.
+pub(crate) fn cordic_sqrt<T: Primitive>(n: T) -> T {
+    // The bits the pipeline's root must have below the point to be within one of the root
+    const GUARD: i32 = 8;
+
+    if n == T::zero() {
+        return n;
+    }
+    let bits = (T::zero().leading_zeros() - n.leading_zeros()) as i32;
+    // n is v * 2^(FRAC + shift), with v from 0.25 to 1
+    let shift = (bits - FRAC as i32 + 1) & !1;
+    let v = if shift > 0 {
+        n.unsigned_shr(shift as u32).to_u64().unwrap()
+    } else {
+        n.to_u64().unwrap() << -shift
+    };
+    let root = cordic_fixed(v);
+    // The root is sqrt(v) * 2^((FRAC + shift) / 2)
+    let half = (shift - FRAC as i32) / 2;
+    if half <= -GUARD {
+        let mut root = T::from(root >> -half).unwrap();
+        let fits = |root: T| root.checked_mul(&root).is_some_and(|square| square <= n);
+        while !fits(root) {
+            root = root - T::one();
+        }
+        while fits(root + T::one()) {
+            root = root + T::one();
+        }
+        root
+    } else {
+        let root = if half < 0 {
+            T::from(root >> -half).unwrap()
+        } else {
+            T::from(root).unwrap().unsigned_shl(half as u32)
+        };
+        // Newton's method needs a guess no smaller than the root, and the pipeline's is within
+        // 2^-24 of it
+        let guess = root + root.unsigned_shr(20) + T::one();
+        newton_sqrt_from(n, guess.min(first_guess(n)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cordic_fixed, cordic_sqrt, FRAC};
+    use newton::newton_sqrt;
+
+    #[test]
+    fn fixed() {
+        // Every 1000th number from 0.25 to 1, 0.5625 and the ends
+        for v in (1u64 << (FRAC - 2)..=1 << FRAC).step_by(1000).chain([
+            9 << 26,
+            (1 << FRAC) - 1,
+            1 << FRAC,
+        ]) {
+            let exact = newton_sqrt(v << FRAC);
+            let root = cordic_fixed(v);
+            // The rounding of the shifts adds up to a few dozen units in the last place
+            assert!(root.abs_diff(exact) <= 32, "{} for {}", root, v);
+        }
+    }
+
+    #[test]
+    fn cordic() {
+        for n in 0..=u16::MAX {
+            assert_eq!(cordic_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for n in 0..=i8::MAX {
+            assert_eq!(cordic_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for n in 0..=u8::MAX {
+            assert_eq!(cordic_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for shift in 0..128 {
+            for n in [1u128 << shift, (1 << shift) - 1, u128::MAX >> shift] {
+                assert_eq!(cordic_sqrt(n), newton_sqrt(n), "in {}", n);
+                let n = (n >> 1) as i128;
+                assert_eq!(cordic_sqrt(n), newton_sqrt(n), "in {}", n);
+                let n = n as u64;
+                assert_eq!(cordic_sqrt(n), newton_sqrt(n), "in {}", n);
+                let n = n as u32;
+                assert_eq!(cordic_sqrt(n), newton_sqrt(n), "in {}", n);
+            }
+        }
+        for root in (0..1u64 << 12).map(|i| u64::MAX - (i << 40)) {
+            let square = u128::from(root) * u128::from(root);
+            for n in [square - 1, square, square + 2 * u128::from(root)] {
+                assert_eq!(cordic_sqrt(n), u128::from(root) - (n < square) as u128);
+            }
+        }
+        for root in (0..1u32 << 12).map(|i| u32::MAX - (i << 20)) {
+            let square = u64::from(root) * u64::from(root);
+            for n in [square - 1, square, square + 2 * u64::from(root)] {
+                assert_eq!(cordic_sqrt(n), u64::from(root) - (n < square) as u64);
+            }
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use core::hint::black_box;
+
+/// Integer square roots that take the same time whatever the number, for roots of secret values
+/// in cryptographic code. This needs the `ct` feature.
+///
+/// The other roots in this crate skip the leading zeros of the number, exit early and correct
+/// float estimates, so their timing depends on the value. These instead run the bit-by-bit
+/// method for every bit pair of the type, choosing each bit of the root with a mask rather than
+/// a branch, and look nothing up in tables.
+///
+/// This is best effort: the compiler promises nothing about the timing of the code it emits, so
+/// the masks are passed through [`black_box`] to keep it from turning them back into branches.
+/// The tests measure the timing as dudect does, but the machine code for a target should still
+/// be checked before relying on it.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::ConstantTimeSqrt;
+///
+/// # fn main() {
+/// assert_eq!(1000u64.integer_sqrt_ct(), 31);
+/// assert_eq!(1000u32.integer_sqrt_rem_ct(), (31, 39));
+/// # }
+/// ```
+///
+/// [`black_box`]: https://doc.rust-lang.org/core/hint/fn.black_box.html
+pub trait ConstantTimeSqrt {
+    /// Find the integer square root, in a time that does not depend on the number.
+    fn integer_sqrt_ct(&self) -> Self
+    where
+        Self: Sized;
+
+    /// Find the integer square root and the remainder `self - root * root`, in a time that does
+    /// not depend on the number.
+    fn integer_sqrt_rem_ct(&self) -> (Self, Self)
+    where
+        Self: Sized;
+}
+
+/// Integer cube and `k`th roots that take the same time whatever the number, for roots of secret
+/// values in cryptographic code. This needs the `ct` feature.
+///
+/// The roots are built a bit at a time from the top, keeping each bit if the power of the root
+/// so far is still at most the number. Every bit the root of the type could have is tried, and
+/// the power is found with the same `k - 1` multiplications each time, so only the degree `k`,
+/// which is taken to be public, affects the timing. As with [`ConstantTimeSqrt`], this is best
+/// effort.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::ConstantTimeRoots;
+///
+/// # fn main() {
+/// assert_eq!(1000u64.integer_cbrt_ct(), 10);
+/// assert_eq!(999u32.nth_root_ct(3), 9);
+/// assert_eq!(u128::MAX.nth_root_ct(5), 50_859_008);
+/// # }
+/// ```
+///
+/// [`ConstantTimeSqrt`]: ./trait.ConstantTimeSqrt.html
+pub trait ConstantTimeRoots {
+    /// Find the integer cube root, in a time that does not depend on the number.
+    fn integer_cbrt_ct(&self) -> Self
+    where
+        Self: Sized;
+
+    /// Find the integer `k`th root, in a time that depends only on `k`.
+    ///
+    /// # Panics
+    ///
+    /// If `k` is zero.
+    fn nth_root_ct(&self, k: u32) -> Self
+    where
+        Self: Sized;
+}
+
+macro_rules! impl_ct {
+    ($($type:ty),*) => {
+        $(
+            impl ConstantTimeSqrt for $type {
+                #[inline]
+                fn integer_sqrt_ct(&self) -> Self {
+                    self.integer_sqrt_rem_ct().0
+                }
+
+                fn integer_sqrt_rem_ct(&self) -> (Self, Self) {
+                    let mut rem = *self;
+                    let mut root: $type = 0;
+                    let mut bit: $type = 1 << (<$type>::BITS - 2);
+                    // Every round runs, even once the remainder is 0
+                    for _ in 0..<$type>::BITS / 2 {
+                        // `root` only has bits above `bit`, so this is `root + bit`
+                        let trial = root | bit;
+                        let (diff, borrow) = rem.overflowing_sub(trial);
+                        // All ones if the trial fits in the remainder, and zero if not
+                        let fits = black_box((borrow as $type).wrapping_sub(1));
+                        rem = (diff & fits) | (rem & !fits);
+                        root = (root >> 1) | (bit & fits);
+                        bit >>= 2;
+                    }
+                    (root, rem)
+                }
+            }
+
+            impl ConstantTimeRoots for $type {
+                #[inline]
+                fn integer_cbrt_ct(&self) -> Self {
+                    self.nth_root_ct(3)
+                }
+
+                fn nth_root_ct(&self, k: u32) -> Self {
+                    assert!(k != 0, "cannot calculate the zeroth root of a number");
+                    let n = *self;
+                    // The root has at most this many bits
+                    let bits = <$type>::BITS.div_ceil(k);
+                    let mut root: $type = 0;
+                    for i in (0..bits).rev() {
+                        let trial = root | 1 << i;
+                        // Overflows are collected rather than exiting on them
+                        let (mut power, mut overflow) = (trial, false);
+                        for _ in 1..k {
+                            let (product, overflows) = power.overflowing_mul(trial);
+                            power = product;
+                            overflow |= overflows;
+                        }
+                        let (_, borrow) = n.overflowing_sub(power);
+                        // All ones if the power fits under the number, and zero if not
+                        let fits = black_box(((borrow | overflow) as $type).wrapping_sub(1));
+                        root |= (1 << i) & fits;
+                    }
+                    root
+                }
+            }
+        )*
+    };
+}
+
+impl_ct!(u32, u64, u128);
+
+#[cfg(test)]
+mod tests {
+    use core::hint::black_box;
+    use std::time::Instant;
+    use std::vec::Vec;
+
+    use super::{ConstantTimeRoots, ConstantTimeSqrt};
+    use {IntegerCubeRoot, IntegerNthRoot, IntegerSquareRoot};
+
+    #[test]
+    fn integer_sqrt_ct() {
+        for n in (0..=u32::from(u16::MAX)).chain(u32::MAX - u32::from(u16::MAX)..=u32::MAX) {
+            assert_eq!(n.integer_sqrt_rem_ct(), n.integer_sqrt_rem(), "in {}", n);
+        }
+        for shift in 0..64 {
+            for n in [1u64 << shift, (1 << shift) - 1, u64::MAX >> shift] {
+                assert_eq!(n.integer_sqrt_rem_ct(), n.integer_sqrt_rem(), "in {}", n);
+                let n = u128::from(n) * u128::from(u64::MAX) + u128::from(n);
+                assert_eq!(n.integer_sqrt_rem_ct(), n.integer_sqrt_rem(), "in {}", n);
+            }
+        }
+        for root in (0..1u64 << 12).map(|i| u64::MAX - (i << 40)) {
+            let square = u128::from(root) * u128::from(root);
+            for n in [square - 1, square, square + 2 * u128::from(root)] {
+                assert_eq!(n.integer_sqrt_ct(), n.integer_sqrt(), "in {}", n);
+            }
+        }
+        assert_eq!(u128::MAX.integer_sqrt_ct(), u128::from(u64::MAX));
+    }
+
+    #[test]
+    fn nth_root_ct() {
+        for n in (0..=u32::from(u16::MAX)).chain(u32::MAX - u32::from(u16::MAX)..=u32::MAX) {
+            assert_eq!(n.integer_cbrt_ct(), n.integer_cbrt(), "in {}", n);
+            for k in 1..=33 {
+                assert_eq!(n.nth_root_ct(k), n.nth_root(k), "in {} for {}", n, k);
+            }
+        }
+        for shift in 0..64 {
+            for n in [1u64 << shift, (1 << shift) - 1, u64::MAX >> shift] {
+                let wide = u128::from(n) * u128::from(u64::MAX) + u128::from(n);
+                for k in 1..=65 {
+                    assert_eq!(n.nth_root_ct(k), n.nth_root(k), "in {} for {}", n, k);
+                    assert_eq!(
+                        wide.nth_root_ct(k),
+                        wide.nth_root(k),
+                        "in {} for {}",
+                        wide,
+                        k
+                    );
+                }
+            }
+        }
+        // Cubes and their neighbours, up to the largest cube in a u128
+        for root in (0..1u128 << 12).map(|i| (1 << 42) + 8_191 - (i << 30)) {
+            let cube = root * root * root;
+            for n in [cube - 1, cube, cube + 1] {
+                assert_eq!(n.integer_cbrt_ct(), n.integer_cbrt(), "in {}", n);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn zeroth_root_ct() {
+        8u32.nth_root_ct(0);
+    }
+
+    /// A dudect-style check: time roots of a fixed number and of random numbers, interleaved at
+    /// random, and compare the two timings with Welch's t-test.
+    ///
+    /// dudect takes a t statistic above 10 as a definite leak. The early exits of the other roots
+    /// give statistics in the hundreds, so a looser bound still catches a leak without failing on
+    /// a noisy machine.
+    macro_rules! check_timing {
+        ($type:ty, $random:expr, $root:expr) => {{
+            const BATCH: usize = 32;
+            let mut state = 0x2545_f491_4f6c_dd1du64;
+            let mut next = move || {
+                // xorshift64*
+                state ^= state >> 12;
+                state ^= state << 25;
+                state ^= state >> 27;
+                state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+            };
+            let mut times: [Vec<f64>; 2] = [Vec::new(), Vec::new()];
+            for _ in 0..20_000 {
+                let class = (next() & 1) as usize;
+                let mut inputs = [0 as $type; BATCH];
+                if class == 1 {
+                    for input in inputs.iter_mut() {
+                        *input = $random(&mut next);
+                    }
+                }
+                let start = Instant::now();
+                for input in inputs.iter() {
+                    black_box($root(black_box(*input)));
+                }
+                times[class].push(start.elapsed().as_nanos() as f64);
+            }
+            // Drop the slowest tenth of each class, which interrupts and the like dominate
+            let stats = |times: &mut Vec<f64>| {
+                times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                times.truncate(times.len() * 9 / 10);
+                let len = times.len() as f64;
+                let mean = times.iter().sum::<f64>() / len;
+                let var = times.iter().map(|t| (t - mean) * (t - mean)).sum::<f64>() / (len - 1.0);
+                (mean, var / len)
+            };
+            let (fixed, random) = times.split_at_mut(1);
+            let ((mean0, var0), (mean1, var1)) = (stats(&mut fixed[0]), stats(&mut random[0]));
+            let t = (mean0 - mean1) / (var0 + var1).sqrt();
+            assert!(t.abs() < 50.0, "t = {} for {}", t, stringify!($root));
+        }};
+    }
+
+    macro_rules! check_timings {
+        ($root:expr) => {{
+            check_timing!(
+                u32,
+                |next: &mut dyn FnMut() -> u64| next() as u32,
+                |n: u32| $root(n)
+            );
+            check_timing!(u64, |next: &mut dyn FnMut() -> u64| next(), |n: u64| $root(
+                n
+            ));
+            check_timing!(
+                u128,
+                |next: &mut dyn FnMut() -> u64| u128::from(next()) << 64 | u128::from(next()),
+                |n: u128| $root(n)
+            );
+        }};
+    }
+
+    #[test]
+    #[ignore = "timing is only meaningful in release mode on a quiet machine"]
+    fn constant_time() {
+        check_timings!(|n: _| ConstantTimeSqrt::integer_sqrt_ct(&n));
+        check_timings!(|n: _| ConstantTimeRoots::integer_cbrt_ct(&n));
+        check_timings!(|n: _| ConstantTimeRoots::nth_root_ct(&n, 5));
+    }
+}
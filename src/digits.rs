@@ -0,0 +1,233 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::iter::FusedIterator;
+
+use wide::Widen;
+
+/// Stream the digits of the square root of `n` in the given `base`, using the classical
+/// digit-by-digit method. This needs the `alloc` feature.
+///
+/// The digits of the integer part come first, and there are
+/// [`integer_digits`](./struct.SqrtDigits.html#method.integer_digits) of them, followed by the
+/// digits after the point, which never end. The state grows by a digit for every digit taken, so
+/// the `k`th digit takes O(k) time.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::sqrt_digits;
+///
+/// # fn main() {
+/// let digits = sqrt_digits(2u8, 10);
+/// assert_eq!(digits.integer_digits(), 1);
+/// let first: Vec<u32> = digits.take(8).collect();
+/// assert_eq!(first, [1, 4, 1, 4, 2, 1, 3, 5]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If `base` is less than 2 or greater than 36.
+pub fn sqrt_digits<T: Widen>(n: T, base: u32) -> SqrtDigits {
+    assert!(
+        (2..=36).contains(&base),
+        "base must be in the range 2 to 36"
+    );
+    let n = n.to_u128().unwrap();
+    let group = u128::from(base * base);
+
+    // The digits of n are taken in groups of two (in base `base`), and each group gives one
+    // digit of the integer part of the root.
+    let mut integer_digits = 1;
+    let mut divisor = 1;
+    while n / divisor >= group {
+        divisor *= group;
+        integer_digits += 1;
+    }
+    SqrtDigits {
+        n,
+        base,
+        divisor,
+        root: Natural::default(),
+        remainder: Natural::default(),
+        integer_digits,
+    }
+}
+
+/// An iterator over the digits of a square root, created by [`sqrt_digits`].
+///
+/// [`sqrt_digits`]: ./fn.sqrt_digits.html
+#[derive(Debug, Clone)]
+pub struct SqrtDigits {
+    /// The remaining groups of digits of the number
+    n: u128,
+    base: u32,
+    /// The place value of the next group of digits, or 0 once they have all been used
+    divisor: u128,
+    /// The digits of the root so far
+    root: Natural,
+    /// The number so far minus `root * root`
+    remainder: Natural,
+    integer_digits: usize,
+}
+
+impl SqrtDigits {
+    /// The number of digits before the point.
+    pub fn integer_digits(&self) -> usize {
+        self.integer_digits
+    }
+}
+
+impl Iterator for SqrtDigits {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let base = self.base;
+        let group = match self.n.checked_div(self.divisor) {
+            Some(group) => {
+                self.n %= self.divisor;
+                self.divisor /= u128::from(base * base);
+                group as u32
+            }
+            None => 0,
+        };
+        let remainder = self.remainder.mul_add(base * base, group);
+
+        // The next digit is the largest `x` with `(2 * root * base + x) * x <= remainder`
+        let twice_root = self.root.mul_add(2 * base, 0);
+        let used = |x: u32| twice_root.mul_add(1, x).mul_add(x, 0);
+        let (mut digit, mut high) = (0, base - 1);
+        while digit < high {
+            let mid = (digit + high).div_ceil(2);
+            if used(mid) <= remainder {
+                digit = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        self.remainder = remainder.sub(&used(digit));
+        self.root = self.root.mul_add(base, digit);
+        Some(digit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl FusedIterator for SqrtDigits {}
+
+/// A natural number of any size, as little-endian 32 bit limbs without leading zero limbs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Natural(Vec<u32>);
+
+impl Natural {
+    /// `self * m + a`.
+    fn mul_add(&self, m: u32, a: u32) -> Natural {
+        let mut limbs = Vec::with_capacity(self.0.len() + 1);
+        let mut carry = u64::from(a);
+        for &limb in self.0.iter() {
+            let value = u64::from(limb) * u64::from(m) + carry;
+            limbs.push(value as u32);
+            carry = value >> 32;
+        }
+        limbs.push(carry as u32);
+        Natural::trimmed(limbs)
+    }
+
+    /// `self - other`, which must not be negative.
+    fn sub(&self, other: &Natural) -> Natural {
+        let mut limbs = Vec::with_capacity(self.0.len());
+        let mut borrow = false;
+        for (i, &limb) in self.0.iter().enumerate() {
+            let (value, under) = limb.overflowing_sub(other.0.get(i).cloned().unwrap_or(0));
+            let (value, under_borrow) = value.overflowing_sub(u32::from(borrow));
+            limbs.push(value);
+            borrow = under | under_borrow;
+        }
+        debug_assert!(!borrow && other.0.len() <= self.0.len());
+        Natural::trimmed(limbs)
+    }
+
+    fn trimmed(mut limbs: Vec<u32>) -> Natural {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        Natural(limbs)
+    }
+}
+
+impl PartialOrd for Natural {
+    fn partial_cmp(&self, other: &Natural) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Natural {
+    fn cmp(&self, other: &Natural) -> Ordering {
+        // Without leading zero limbs, the longer number is the larger
+        self.0
+            .len()
+            .cmp(&other.0.len())
+            .then_with(|| self.0.iter().rev().cmp(other.0.iter().rev()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sqrt_digits;
+    use IntegerSquareRoot;
+
+    #[test]
+    fn sqrt_2() {
+        let expected = "1414213562373095048801688724209698078569671875376948073176679737990732478462107038850387534327641572";
+        let mut digits = sqrt_digits(2u32, 10);
+        for c in expected.chars() {
+            assert_eq!(digits.next(), c.to_digit(10));
+        }
+        // The 9,981st to 10,000th digits
+        let expected = "46555323028587325835";
+        let mut digits = digits.skip(10_000 - 100 - 20);
+        for c in expected.chars() {
+            assert_eq!(digits.next(), c.to_digit(10));
+        }
+    }
+
+    #[test]
+    fn integer_part() {
+        for base in 2..=36 {
+            for n in (0..=u16::MAX).step_by(17) {
+                let digits = sqrt_digits(n, base);
+                let integer_digits = digits.integer_digits();
+                let root = digits
+                    .take(integer_digits)
+                    .fold(0, |root, digit| root * base + digit);
+                assert_eq!(root, u32::from(n.integer_sqrt()), "n {} base {}", n, base);
+            }
+        }
+        let digits = sqrt_digits(u128::MAX, 16);
+        assert_eq!(digits.integer_digits(), 16);
+        assert!(digits.take(16).all(|digit| digit == 15));
+    }
+
+    #[test]
+    fn fraction() {
+        let digits = sqrt_digits(1u8, 2);
+        assert!(digits.skip(1).take(1000).all(|digit| digit == 0));
+        let mut digits = sqrt_digits(10u8, 3);
+        assert_eq!(digits.integer_digits(), 2);
+        // sqrt(10) = 3.1622..., which is 10.011101... in base 3
+        let first: [u32; 8] = [1, 0, 0, 1, 1, 1, 0, 1];
+        for &digit in first.iter() {
+            assert_eq!(digits.next(), Some(digit));
+        }
+        // Past the 36 or so digits that fit in 128 bits
+        let digits = sqrt_digits(u128::MAX, 36);
+        assert_eq!(digits.skip(1000).take(1000).count(), 1000);
+    }
+
+    #[test]
+    #[should_panic]
+    fn base_too_large() {
+        sqrt_digits(2u8, 37);
+    }
+}
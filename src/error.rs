@@ -0,0 +1,86 @@
+use core::fmt;
+
+/// The error returned when trying to take the square root of a negative number.
+///
+/// It carries the offending value, which can be retrieved with [`value`].
+///
+/// [`value`]: #method.value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NegativeSqrtError<T> {
+    value: T,
+}
+
+impl<T> NegativeSqrtError<T> {
+    pub(crate) fn new(value: T) -> Self {
+        NegativeSqrtError { value }
+    }
+
+    /// The negative number whose square root was requested.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for NegativeSqrtError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cannot calculate square root of negative number {}",
+            self.value
+        )
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> core::error::Error for NegativeSqrtError<T> {}
+
+/// The error returned by [`isqrt_f64`] when the float does not hold a suitable integer.
+///
+/// [`isqrt_f64`]: ./fn.isqrt_f64.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FloatSqrtError {
+    /// The number is negative.
+    Negative,
+    /// The number is not an integer, or is NaN.
+    NotInteger,
+    /// The number is at least 2^128, so its square root does not fit in a `u64`.
+    TooLarge,
+}
+
+impl fmt::Display for FloatSqrtError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            FloatSqrtError::Negative => "cannot calculate square root of negative number",
+            FloatSqrtError::NotInteger => "cannot calculate integer square root of non-integer",
+            FloatSqrtError::TooLarge => "square root is too large to fit in a u64",
+        })
+    }
+}
+
+impl core::error::Error for FloatSqrtError {}
+
+#[cfg(test)]
+mod tests {
+    use std::string::ToString;
+    use FloatSqrtError;
+    use IntegerSquareRoot;
+
+    #[test]
+    fn try_integer_sqrt() {
+        assert_eq!(17u8.try_integer_sqrt(), Ok(4));
+        assert_eq!(17i64.try_integer_sqrt(), Ok(4));
+        let err = (-17i64).try_integer_sqrt().unwrap_err();
+        assert_eq!(*err.value(), -17);
+        assert_eq!(
+            err.to_string(),
+            "cannot calculate square root of negative number -17"
+        );
+    }
+
+    #[test]
+    fn float_sqrt_error() {
+        assert_eq!(
+            FloatSqrtError::NotInteger.to_string(),
+            "cannot calculate integer square root of non-integer"
+        );
+    }
+}
@@ -0,0 +1,396 @@
+use primitive::Primitive;
+use IntegerCubeRoot;
+use IntegerSquareRoot;
+
+/// A trait for inverting figurate numbers, i.e. finding the index of the largest figurate number
+/// of a given kind that is no greater than a number.
+pub trait FigurateRoots {
+    /// Find the triangular root, the largest `k` such that `k * (k + 1) / 2 <= self`.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    fn triangular_root(&self) -> Self
+    where
+        Self: Sized,
+    {
+        self.triangular_root_checked()
+            .expect("cannot calculate triangular root of negative number")
+    }
+
+    /// Find the triangular root, returning `None` if the number is negative (this can never
+    /// happen for unsigned types).
+    fn triangular_root_checked(&self) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Find the polygonal root for polygons with `sides` sides, the largest `k` such that the
+    /// `k`th `sides`-gonal number, `((sides - 2) * k * k - (sides - 4) * k) / 2`, is `<= self`.
+    ///
+    /// For example `sides == 3` gives the [triangular root] and `sides == 4` the integer square
+    /// root.
+    ///
+    /// # Panics
+    ///
+    /// If `sides < 3`, or for negative numbers (`i` family).
+    ///
+    /// [triangular root]: #method.triangular_root
+    fn polygonal_root(&self, sides: u32) -> Self
+    where
+        Self: Sized,
+    {
+        assert!(sides >= 3, "a polygon must have at least 3 sides");
+        self.polygonal_root_checked(sides)
+            .expect("cannot calculate polygonal root of negative number")
+    }
+
+    /// Find the polygonal root for polygons with `sides` sides, returning `None` if `sides < 3`
+    /// or if the number is negative (the latter can never happen for unsigned types).
+    fn polygonal_root_checked(&self, sides: u32) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Find the tetrahedral root, the largest `k` such that `k * (k + 1) * (k + 2) / 6 <= self`.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    fn tetrahedral_root(&self) -> Self
+    where
+        Self: Sized,
+    {
+        self.tetrahedral_root_checked()
+            .expect("cannot calculate tetrahedral root of negative number")
+    }
+
+    /// Find the tetrahedral root, returning `None` if the number is negative (this can never
+    /// happen for unsigned types).
+    fn tetrahedral_root_checked(&self) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Find the square pyramidal root, the largest `k` such that
+    /// `k * (k + 1) * (2 * k + 1) / 6 <= self`.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    fn square_pyramidal_root(&self) -> Self
+    where
+        Self: Sized,
+    {
+        self.square_pyramidal_root_checked()
+            .expect("cannot calculate square pyramidal root of negative number")
+    }
+
+    /// Find the square pyramidal root, returning `None` if the number is negative (this can
+    /// never happen for unsigned types).
+    fn square_pyramidal_root_checked(&self) -> Option<Self>
+    where
+        Self: Sized;
+}
+
+impl<T: Primitive> FigurateRoots for T {
+    fn triangular_root_checked(&self) -> Option<Self> {
+        let n = *self;
+        if n < T::zero() {
+            return None;
+        }
+        // k is the largest solution of k * (k + 1) <= 2n, and s = isqrt(2n) is either k or k + 1.
+        // 2n may not fit in T, so take the root of m = n / 2 instead: with m = r^2 + rem and
+        // b = n % 2, 2n = 4r^2 + 4rem + 2b, which lies below (2r + 2)^2 and reaches (2r + 1)^2
+        // exactly when rem + b > r.
+        let (r, rem) = n.unsigned_shr(1).integer_sqrt_rem();
+        let b = n & T::one();
+        let s = if rem + b > r { r + r + T::one() } else { r + r };
+        if triangular(s).is_some_and(|t| t <= n) {
+            Some(s)
+        } else {
+            Some(s - T::one())
+        }
+    }
+
+    fn polygonal_root_checked(&self, sides: u32) -> Option<Self> {
+        let n = *self;
+        match sides {
+            0..=2 => return None,
+            3 => return n.triangular_root_checked(),
+            4 => return n.integer_sqrt_checked(),
+            _ => {}
+        }
+        if n < T::zero() {
+            return None;
+        }
+        // With d = sides - 2, the kth polygonal number is (d * k^2 - (d - 2) * k) / 2. If d does
+        // not even fit in T, then every polygonal number after the first (the second is `sides`)
+        // is too large.
+        let d = match T::from(sides - 2) {
+            Some(d) => d,
+            None => return Some(if n == T::zero() { T::zero() } else { T::one() }),
+        };
+
+        // The root is within a couple of sqrt(2n / d), which we approximate by isqrt(2 * (n / d))
+        // (d >= 3, so this cannot overflow), and then correct by stepping.
+        let estimate = (n / d + n / d).integer_sqrt();
+        Some(correct_root(estimate, |k| {
+            polygonal(d, k).is_some_and(|p| p <= n)
+        }))
+    }
+
+    fn tetrahedral_root_checked(&self) -> Option<Self> {
+        let n = *self;
+        if n < T::zero() {
+            return None;
+        }
+        // k^3 < 6 * tetrahedral(k) < (k + 2)^3, so the root is just below cbrt(6n). 6n may not
+        // fit in T, but cbrt(6n) = 2 * cbrt(3n / 4) and 3n / 4 = n - n / 4 does.
+        let estimate = (n - n.unsigned_shr(2)).integer_cbrt();
+        Some(correct_root(estimate + estimate, |k| {
+            tetrahedral(k).is_some_and(|t| t <= n)
+        }))
+    }
+
+    fn square_pyramidal_root_checked(&self) -> Option<Self> {
+        let n = *self;
+        if n < T::zero() {
+            return None;
+        }
+        // 2k^3 < 6 * square_pyramidal(k) < 2(k + 1)^3, so the root is just below cbrt(3n).
+        // 3n may not fit in T, but cbrt(3n) = 2 * cbrt(3n / 8) and 3n / 8 = n / 4 + n / 8
+        // does.
+        let estimate = (n.unsigned_shr(2) + n.unsigned_shr(3)).integer_cbrt();
+        Some(correct_root(estimate + estimate, |k| {
+            square_pyramidal(k).is_some_and(|p| p <= n)
+        }))
+    }
+}
+
+/// Step from an estimate of a root to the largest `k` for which `fits(k)` holds, where `fits`
+/// holds for 0 and is monotonic.
+fn correct_root<T: Primitive, F: Fn(T) -> bool>(estimate: T, fits: F) -> T {
+    let mut k = estimate;
+    while !fits(k) {
+        k = k - T::one();
+    }
+    while fits(k + T::one()) {
+        k = k + T::one();
+    }
+    k
+}
+
+/// `k * (k + 1) * (k + 2) / 6`, or `None` if it overflows
+fn tetrahedral<T: Primitive>(k: T) -> Option<T> {
+    // One of k, k + 1, k + 2 is divisible by 3: if it is not k + 2, it divides k * (k + 1) / 2
+    let three = T::from(3)?;
+    let t = triangular(k)?;
+    let last = k + T::one() + T::one();
+    if last % three == T::zero() {
+        t.checked_mul(&(last / three))
+    } else {
+        (t / three).checked_mul(&last)
+    }
+}
+
+/// `k * (k + 1) * (2k + 1) / 6`, or `None` if it overflows
+fn square_pyramidal<T: Primitive>(k: T) -> Option<T> {
+    // 3 divides one of k, k + 1, 2k + 1: if it is not 2k + 1, it divides k * (k + 1) / 2
+    let three = T::from(3)?;
+    let t = triangular(k)?;
+    let last = k.checked_add(&k)?.checked_add(&T::one())?;
+    if last % three == T::zero() {
+        t.checked_mul(&(last / three))
+    } else {
+        (t / three).checked_mul(&last)
+    }
+}
+
+/// The `k`th polygonal number for polygons with `d + 2` sides, or `None` if it overflows
+fn polygonal<T: Primitive>(d: T, k: T) -> Option<T> {
+    if k == T::zero() {
+        return Some(T::zero());
+    }
+    // (d * k^2 - (d - 2) * k) / 2 = k * (d * (k - 1) + 2) / 2, where the product is even, so
+    // halve whichever factor is even first
+    let other = d
+        .checked_mul(&(k - T::one()))?
+        .checked_add(&(T::one() + T::one()))?;
+    if k & T::one() == T::zero() {
+        k.unsigned_shr(1).checked_mul(&other)
+    } else {
+        k.checked_mul(&other.unsigned_shr(1))
+    }
+}
+
+/// `k * (k + 1) / 2`, or `None` if it overflows
+fn triangular<T: Primitive>(k: T) -> Option<T> {
+    // One of k and k + 1 is even, so halve that one first
+    let next = k + T::one();
+    if k & T::one() == T::zero() {
+        k.unsigned_shr(1).checked_mul(&next)
+    } else {
+        k.checked_mul(&next.unsigned_shr(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FigurateRoots;
+
+    macro_rules! gen_tests {
+        ($($type:ty => $fn_name:ident),*) => {
+            $(
+                #[test]
+                fn $fn_name() {
+                    let is_triangular_root_of = |k: $type, n: $type| {
+                        let t = |k: $type| {
+                            let (a, b) = if k % 2 == 0 { (k / 2, k + 1) } else { (k, k / 2 + 1) };
+                            a.checked_mul(b)
+                        };
+                        t(k).map_or(false, |t| t <= n) && t(k + 1).map_or(true, |t| t > n)
+                    };
+                    let tests: [($type, $type); 8] = [
+                        (0, 0),
+                        (1, 1),
+                        (2, 1),
+                        (3, 2),
+                        (5, 2),
+                        (6, 3),
+                        (9, 3),
+                        (10, 4),
+                    ];
+                    for &(in_, out) in tests.iter() {
+                        assert_eq!(in_.triangular_root(), out, "in {}", in_);
+                    }
+                    for &in_ in [<$type>::MAX, <$type>::MAX - 1, <$type>::MAX / 2 + 1].iter() {
+                        assert!(is_triangular_root_of(in_.triangular_root(), in_), "in {}", in_);
+                    }
+                }
+            )*
+        };
+    }
+
+    gen_tests! {
+        i8 => i8_test,
+        u8 => u8_test,
+        i16 => i16_test,
+        u16 => u16_test,
+        i32 => i32_test,
+        u32 => u32_test,
+        i64 => i64_test,
+        u64 => u64_test,
+        i128 => i128_test,
+        u128 => u128_test,
+        isize => isize_test,
+        usize => usize_test
+    }
+
+    #[test]
+    fn exhaustive_u16() {
+        let mut k = 0u32;
+        for n in 0..=u16::MAX {
+            while (k + 1) * (k + 2) / 2 <= u32::from(n) {
+                k += 1;
+            }
+            assert_eq!(u32::from(n.triangular_root()), k, "in {}", n);
+        }
+    }
+
+    #[test]
+    fn polygonal_exhaustive_u16() {
+        for sides in 3..40u32 {
+            let polygonal = |k: u64| ((u64::from(sides) - 2) * k * k.saturating_sub(1) + 2 * k) / 2;
+            let mut k = 0u64;
+            for n in 0..=u16::MAX {
+                while polygonal(k + 1) <= u64::from(n) {
+                    k += 1;
+                }
+                assert_eq!(
+                    u64::from(n.polygonal_root(sides)),
+                    k,
+                    "in {} sides {}",
+                    n,
+                    sides
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn polygonal_extremes() {
+        // 5-gonal: 1, 5, 12, 22, ...
+        assert_eq!(4u8.polygonal_root(5), 1);
+        assert_eq!(5u8.polygonal_root(5), 2);
+        assert_eq!(u8::MAX.polygonal_root(1000), 1);
+        assert_eq!(0u8.polygonal_root(u32::MAX), 0);
+        assert_eq!(u128::MAX.polygonal_root(4), u64::MAX as u128);
+        for &sides in [5u32, 6, 7, 100, 1 << 20, u32::MAX].iter() {
+            for &n in [u64::MAX, u64::MAX - 1, u64::MAX / 2].iter() {
+                let k = u128::from(n.polygonal_root(sides));
+                let d = u128::from(sides) - 2;
+                let polygonal = |k: u128| (d * k * k.saturating_sub(1) + 2 * k) / 2;
+                assert!(polygonal(k) <= u128::from(n), "in {} sides {}", n, sides);
+                assert!(polygonal(k + 1) > u128::from(n), "in {} sides {}", n, sides);
+            }
+        }
+        // 6-gonal numbers are k * (2k - 1)
+        let k = i128::from(i64::MAX.polygonal_root(6));
+        assert!(k * (2 * k - 1) <= i128::from(i64::MAX));
+        assert!((k + 1) * (2 * k + 1) > i128::from(i64::MAX));
+    }
+
+    #[test]
+    fn pyramidal_exhaustive_u16() {
+        let mut tetrahedral = 0u64;
+        let mut pyramidal = 0u64;
+        for n in 0..=u16::MAX {
+            let n64 = u64::from(n);
+            while (tetrahedral + 1) * (tetrahedral + 2) * (tetrahedral + 3) / 6 <= n64 {
+                tetrahedral += 1;
+            }
+            while (pyramidal + 1) * (pyramidal + 2) * (2 * pyramidal + 3) / 6 <= n64 {
+                pyramidal += 1;
+            }
+            assert_eq!(u64::from(n.tetrahedral_root()), tetrahedral, "in {}", n);
+            assert_eq!(u64::from(n.square_pyramidal_root()), pyramidal, "in {}", n);
+        }
+    }
+
+    #[test]
+    fn pyramidal_extremes() {
+        macro_rules! check {
+            ($($type:ty),*) => {
+                $(
+                    for &n in [<$type>::MAX, <$type>::MAX - 1, <$type>::MAX / 5].iter() {
+                        let big = |k: $type| k as u128;
+                        let tetrahedral = |k: u128| k * (k + 1) * (k + 2) / 6;
+                        let pyramidal = |k: u128| k * (k + 1) * (2 * k + 1) / 6;
+                        let k = big(n.tetrahedral_root());
+                        assert!(tetrahedral(k) <= big(n) && tetrahedral(k + 1) > big(n));
+                        let k = big(n.square_pyramidal_root());
+                        assert!(pyramidal(k) <= big(n) && pyramidal(k + 1) > big(n));
+                    }
+                )*
+            };
+        }
+        check!(u8, i8, u16, i16, u32, i32, u64, i64);
+        // For u128, compute t * m / 3 as (t / 3) * m + (t % 3) * m / 3 to avoid overflowing
+        let checked = |t: u128, m: u128| (t / 3).checked_mul(m)?.checked_add((t % 3) * m / 3);
+        let tetrahedral = |k: u128| checked(k * (k + 1) / 2, k + 2);
+        let pyramidal = |k: u128| checked(k * (k + 1) / 2, 2 * k + 1);
+        for &n in [u128::MAX, u128::MAX - 1, u128::MAX / 5].iter() {
+            let k = n.tetrahedral_root();
+            assert!(tetrahedral(k).unwrap() <= n && tetrahedral(k + 1).map_or(true, |t| t > n));
+            let k = n.square_pyramidal_root();
+            assert!(pyramidal(k).unwrap() <= n && pyramidal(k + 1).map_or(true, |p| p > n));
+        }
+    }
+
+    #[test]
+    fn negative() {
+        assert_eq!((-1i32).tetrahedral_root_checked(), None);
+        assert_eq!((-1i32).square_pyramidal_root_checked(), None);
+        assert_eq!((-1i32).triangular_root_checked(), None);
+        assert_eq!((-1i32).polygonal_root_checked(7), None);
+        assert_eq!(1i32.polygonal_root_checked(2), None);
+    }
+}
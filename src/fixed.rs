@@ -0,0 +1,318 @@
+use wide::{wide_mul_narrow, wide_shl, Widen};
+
+/// Find the integer reciprocal square root of `n` scaled by `2^k`, i.e. the integer part of
+/// `2^k / sqrt(n)`.
+///
+/// This is the reciprocal square root as a fixed point number with `k` fractional bits. It is
+/// implemented for the unsigned types (`u` family), for `k` less than their number of bits.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::integer_rsqrt_scaled;
+///
+/// # fn main() {
+/// // 1 / sqrt(2) = 0.7071..., and 0.7071 * 2^16 = 46340.95...
+/// assert_eq!(integer_rsqrt_scaled(2u32, 16), 46340);
+/// assert_eq!(integer_rsqrt_scaled(16u32, 4), 4);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If `n` is zero, or if `k` is not less than the number of bits in `T`.
+#[inline]
+pub fn integer_rsqrt_scaled<T: Widen>(n: T, k: u32) -> T {
+    integer_rsqrt_scaled_checked(n, k).expect("cannot calculate reciprocal square root")
+}
+
+/// Find the integer reciprocal square root of `n` scaled by `2^k`, returning `None` if `n` is
+/// zero or if `k` is not less than the number of bits in `T`.
+pub fn integer_rsqrt_scaled_checked<T: Widen>(n: T, k: u32) -> Option<T> {
+    if n == T::zero() || k >= T::zero().count_zeros() {
+        return None;
+    }
+
+    // The result is the largest `r` such that `r * r * n <= 4^k`, and is at most `2^k`, so
+    // decide its `k + 1` bits one at a time from the top. Overflow of the product means "too
+    // big", because `4^k` always fits in double width.
+    let target = wide_shl(T::one(), 2 * k)?;
+    let mut bit = T::one().unsigned_shl(k);
+    let mut result = T::zero();
+    while bit != T::zero() {
+        let candidate = result | bit;
+        match wide_mul_narrow(candidate.wide_mul(candidate), n) {
+            Some(product) if product <= target => result = candidate,
+            _ => {}
+        }
+        bit = bit.unsigned_shr(1);
+    }
+    Some(result)
+}
+
+/// Find the integer reciprocal square root of `n` scaled by `2^k`, saturating to `T::max_value()`
+/// when `n` is zero, whose reciprocal square root is infinite.
+///
+/// # Panics
+///
+/// If `k` is not less than the number of bits in `T`.
+pub fn integer_rsqrt_scaled_saturating<T: Widen>(n: T, k: u32) -> T {
+    assert!(
+        k < T::zero().count_zeros(),
+        "cannot calculate reciprocal square root"
+    );
+    integer_rsqrt_scaled_checked(n, k).unwrap_or_else(T::max_value)
+}
+
+/// Find the square root of `n` with `frac_bits` binary fractional digits, i.e. the integer part
+/// of `sqrt(n) * 2^frac_bits`.
+///
+/// This is the square root of an integer as a fixed point number. It is implemented for the
+/// unsigned types (`u` family).
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::sqrt_fixed;
+///
+/// # fn main() {
+/// // sqrt(2) = 1.4142..., and 1.4142 * 2^8 = 362.03...
+/// assert_eq!(sqrt_fixed(2u32, 8), 362);
+/// assert_eq!(sqrt_fixed(9u32, 8), 3 << 8);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If the result does not fit in `T`.
+#[inline]
+pub fn sqrt_fixed<T: Widen>(n: T, frac_bits: u32) -> T {
+    sqrt_fixed_checked(n, frac_bits).expect("fixed point square root overflowed")
+}
+
+/// Find the square root of `n` with `frac_bits` binary fractional digits, returning `None` if
+/// the result does not fit in `T`.
+#[inline]
+pub fn sqrt_fixed_checked<T: Widen>(n: T, frac_bits: u32) -> Option<T> {
+    // This is the integer square root of `n * 4^frac_bits`, which overflows double width
+    // exactly when the root would not fit.
+    let (high, low) = wide_shl(n, frac_bits.checked_mul(2)?)?;
+    Some(T::wide_sqrt(high, low))
+}
+
+/// Find the square root of `n` with `frac_bits` binary fractional digits, saturating to
+/// `T::max_value()` if the result does not fit in `T`.
+#[inline]
+pub fn sqrt_fixed_saturating<T: Widen>(n: T, frac_bits: u32) -> T {
+    sqrt_fixed_checked(n, frac_bits).unwrap_or_else(T::max_value)
+}
+
+/// Find the square root of `n` with `decimals` decimal fractional digits, i.e. the integer part
+/// of `sqrt(n) * 10^decimals`.
+///
+/// This is the square root of an integer at a decimal scale, for example in hundredths with
+/// `decimals` of 2. It is implemented for the unsigned types (`u` family).
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::sqrt_decimal_scaled;
+///
+/// # fn main() {
+/// // sqrt(2) = 1.41421...
+/// assert_eq!(sqrt_decimal_scaled(2u32, 4), 14142);
+/// assert_eq!(sqrt_decimal_scaled(9u32, 2), 300);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If the result does not fit in `T`.
+#[inline]
+pub fn sqrt_decimal_scaled<T: Widen>(n: T, decimals: u32) -> T {
+    sqrt_decimal_scaled_checked(n, decimals).expect("decimal scaled square root overflowed")
+}
+
+/// Find the square root of `n` with `decimals` decimal fractional digits, returning `None` if
+/// the result does not fit in `T`.
+pub fn sqrt_decimal_scaled_checked<T: Widen>(n: T, decimals: u32) -> Option<T> {
+    if n == T::zero() {
+        return Some(T::zero());
+    }
+
+    // This is the integer square root of `n * 100^decimals`, which overflows double width
+    // exactly when the root would not fit.
+    let hundred = T::from(100).unwrap();
+    let mut scaled = (T::zero(), n);
+    for _ in 0..decimals {
+        scaled = wide_mul_narrow(scaled, hundred)?;
+    }
+    Some(T::wide_sqrt(scaled.0, scaled.1))
+}
+
+/// Find the square root of `n` with `decimals` decimal fractional digits, saturating to
+/// `T::max_value()` if the result does not fit in `T`.
+#[inline]
+pub fn sqrt_decimal_scaled_saturating<T: Widen>(n: T, decimals: u32) -> T {
+    sqrt_decimal_scaled_checked(n, decimals).unwrap_or_else(T::max_value)
+}
+
+/// Find the integer square root of the ratio `numerator / denominator`, i.e. the integer part
+/// of `sqrt(numerator / denominator)` with the division done exactly.
+///
+/// It is implemented for the unsigned types (`u` family).
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::sqrt_ratio;
+///
+/// # fn main() {
+/// // sqrt(50 / 2) = 5, and sqrt(99 / 4) = 4.97...
+/// assert_eq!(sqrt_ratio(50u32, 2), 5);
+/// assert_eq!(sqrt_ratio(99u32, 4), 4);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If `denominator` is zero.
+#[inline]
+pub fn sqrt_ratio<T: Widen>(numerator: T, denominator: T) -> T {
+    sqrt_ratio_checked(numerator, denominator).expect("cannot divide by zero")
+}
+
+/// Find the integer square root of the ratio `numerator / denominator`, returning `None` if
+/// `denominator` is zero.
+#[inline]
+pub fn sqrt_ratio_checked<T: Widen>(numerator: T, denominator: T) -> Option<T> {
+    // The result is the largest `r` such that `r * r <= numerator / denominator`. `r * r` is an
+    // integer, so this is the same as `r * r <= floor(numerator / denominator)`, and dividing
+    // first loses nothing.
+    let quotient = numerator.checked_div(&denominator)?;
+    Some(T::wide_sqrt(T::zero(), quotient))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        integer_rsqrt_scaled, integer_rsqrt_scaled_checked, integer_rsqrt_scaled_saturating,
+        sqrt_decimal_scaled, sqrt_decimal_scaled_checked, sqrt_decimal_scaled_saturating,
+        sqrt_fixed, sqrt_fixed_checked, sqrt_fixed_saturating, sqrt_ratio, sqrt_ratio_checked,
+    };
+    use IntegerSquareRoot;
+
+    #[test]
+    fn rsqrt_scaled() {
+        for n in 1..=u8::MAX {
+            for k in 0..8 {
+                let r = u32::from(integer_rsqrt_scaled(n, k));
+                let (n, target) = (u32::from(n), 1 << (2 * k));
+                assert!(
+                    r * r * n <= target && (r + 1) * (r + 1) * n > target,
+                    "n {} k {}",
+                    n,
+                    k
+                );
+            }
+        }
+        assert_eq!(integer_rsqrt_scaled_checked(0u32, 4), None);
+        assert_eq!(integer_rsqrt_scaled_checked(1u32, 32), None);
+        assert_eq!(integer_rsqrt_scaled(1u32, 31), 1 << 31);
+        assert_eq!(integer_rsqrt_scaled(4u64, 63), 1 << 62);
+        assert_eq!(integer_rsqrt_scaled(u64::MAX, 63), 2147483648);
+        assert_eq!(
+            integer_rsqrt_scaled(3u128, 127),
+            98231058071143413794695818561922359105
+        );
+        assert_eq!(integer_rsqrt_scaled(u128::MAX, 127), 1 << 63);
+    }
+
+    #[test]
+    fn fixed() {
+        for n in 0..=u8::MAX {
+            for frac_bits in 0..=8 {
+                let root = (u64::from(n) << (2 * frac_bits)).integer_sqrt();
+                assert_eq!(
+                    sqrt_fixed_checked(n, frac_bits).map(u64::from),
+                    Some(root).filter(|&r| r <= 255),
+                    "n {} frac_bits {}",
+                    n,
+                    frac_bits
+                );
+            }
+        }
+        assert_eq!(sqrt_fixed(0u8, 1000), 0);
+        assert_eq!(sqrt_fixed_checked(1u8, u32::MAX), None);
+        assert_eq!(sqrt_fixed(1u64, 63), 1 << 63);
+        assert_eq!(sqrt_fixed_checked(1u64, 64), None);
+        assert_eq!(sqrt_fixed(2u64, 32), 6074000999);
+        assert_eq!(sqrt_fixed(u128::MAX, 0), u64::MAX as u128);
+        assert_eq!(sqrt_fixed(u128::MAX, 64), u128::MAX);
+        assert_eq!(
+            sqrt_fixed(2u128, 126),
+            120307984584002255772516886238812528463
+        );
+    }
+
+    #[test]
+    fn ratio() {
+        for n in 0..=u8::MAX {
+            for d in 1..=u8::MAX {
+                let r = u32::from(sqrt_ratio(n, d));
+                let (n, d) = (u32::from(n), u32::from(d));
+                assert!(
+                    r * r * d <= n && (r + 1) * (r + 1) * d > n,
+                    "n {} d {}",
+                    n,
+                    d
+                );
+            }
+        }
+        assert_eq!(sqrt_ratio_checked(10u32, 0), None);
+        assert_eq!(sqrt_ratio(u128::MAX, 1), u64::MAX as u128);
+        assert_eq!(sqrt_ratio(u128::MAX, 4), (u64::MAX >> 1) as u128);
+        assert_eq!(sqrt_ratio(u128::MAX, u128::MAX), 1);
+    }
+
+    #[test]
+    fn decimal_scaled() {
+        for n in 0..=u8::MAX {
+            for decimals in 0..=3 {
+                let root = (u64::from(n) * 100u64.pow(decimals)).integer_sqrt();
+                assert_eq!(
+                    sqrt_decimal_scaled_checked(n, decimals).map(u64::from),
+                    Some(root).filter(|&r| r <= 255),
+                    "n {} decimals {}",
+                    n,
+                    decimals
+                );
+            }
+        }
+        assert_eq!(sqrt_decimal_scaled(0u8, u32::MAX), 0);
+        assert_eq!(sqrt_decimal_scaled(2u64, 18), 1414213562373095048);
+        assert_eq!(sqrt_decimal_scaled_checked(4u64, 19), None);
+        assert_eq!(sqrt_decimal_scaled(1u128, 38), 10u128.pow(38));
+        assert_eq!(sqrt_decimal_scaled_checked(16u128, 38), None);
+        assert_eq!(sqrt_decimal_scaled(u128::MAX, 0), u64::MAX as u128);
+    }
+
+    #[test]
+    fn saturating() {
+        for n in 0..=u8::MAX {
+            for scale in 0..=8 {
+                let fixed = sqrt_fixed_checked(n, scale).unwrap_or(u8::MAX);
+                assert_eq!(sqrt_fixed_saturating(n, scale), fixed);
+                let decimal = sqrt_decimal_scaled_checked(n, scale).unwrap_or(u8::MAX);
+                assert_eq!(sqrt_decimal_scaled_saturating(n, scale), decimal);
+            }
+        }
+        assert_eq!(sqrt_fixed_saturating(2u64, 32), 6074000999);
+        assert_eq!(sqrt_fixed_saturating(1u64, 64), u64::MAX);
+        assert_eq!(sqrt_decimal_scaled_saturating(16u128, 38), u128::MAX);
+        assert_eq!(integer_rsqrt_scaled_saturating(0u32, 4), u32::MAX);
+        assert_eq!(integer_rsqrt_scaled_saturating(4u64, 63), 1 << 62);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rsqrt_saturating_large_scale() {
+        integer_rsqrt_scaled_saturating(0u32, 32);
+    }
+}
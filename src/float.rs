@@ -0,0 +1,349 @@
+use error::FloatSqrtError;
+#[cfg(any(
+    feature = "std",
+    feature = "libm",
+    all(feature = "sse2", target_feature = "sse2"),
+    all(feature = "vsqrt-f64", target_arch = "arm", target_abi = "eabihf"),
+    test
+))]
+use primitive::Primitive;
+use IntegerSquareRoot;
+
+#[cfg(all(feature = "sse2", target_feature = "sse2", target_arch = "x86"))]
+use core::arch::x86 as arch;
+#[cfg(all(feature = "sse2", target_feature = "sse2", target_arch = "x86_64"))]
+use core::arch::x86_64 as arch;
+
+/// Find the integer square root of an `f64` holding an integer, such as a number read from JSON.
+///
+/// Floats above 2^53 can still hold integers exactly, but `x.sqrt() as u64` rounds the root and
+/// can be off by one either way for them. Here the float is converted to the integer it holds,
+/// which is always exact for floats below 2^128, and the root of that is taken, so the result is
+/// exact.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::{isqrt_f64, FloatSqrtError};
+///
+/// # fn main() {
+/// assert_eq!(isqrt_f64(17.0), Ok(4));
+/// // 2^64 - 2^11 is the largest float below 2^64, whose root is just under 2^32
+/// assert_eq!(isqrt_f64(18_446_744_073_709_549_568.0), Ok(4_294_967_295));
+/// assert_eq!(isqrt_f64(2.5), Err(FloatSqrtError::NotInteger));
+/// assert_eq!(isqrt_f64(-4.0), Err(FloatSqrtError::Negative));
+/// # }
+/// ```
+pub fn isqrt_f64(x: f64) -> Result<u64, FloatSqrtError> {
+    // 2^128, the smallest float whose root does not fit in a u64
+    const LIMIT: f64 = 340_282_366_920_938_463_463_374_607_431_768_211_456.0;
+
+    if x.is_nan() {
+        Err(FloatSqrtError::NotInteger)
+    } else if x < 0.0 {
+        Err(FloatSqrtError::Negative)
+    } else if x >= LIMIT {
+        Err(FloatSqrtError::TooLarge)
+    } else {
+        let n = x as u128;
+        if n as f64 == x {
+            Ok(n.integer_sqrt() as u64)
+        } else {
+            Err(FloatSqrtError::NotInteger)
+        }
+    }
+}
+
+/// Find the integer square root of non-negative `n`, of at most 64 bits, from its floating point
+/// square root.
+///
+/// Types of up to 32 bits use `f32`, which is cheaper on targets with only single precision
+/// hardware, and wider types use `f64`. Converting `n` to the float rounds it to 24 or 53
+/// significant bits and the square root rounds again, but the result is still within 1 of the
+/// true root. For example `u64::max_value()` rounds up to 2^64, whose root 2^32 is one too big.
+/// The candidate is corrected by comparing squares.
+///
+/// Nothing relies on the float arithmetic being correctly rounded, only on the truncated estimate
+/// being within 1 of the root, and the correction itself is integer arithmetic. On targets such
+/// as `i586`, where the x87 unit keeps excess precision and rounds twice, the float root is still
+/// far closer than that, so those targets take this path too.
+#[cfg(any(
+    feature = "std",
+    feature = "libm",
+    all(feature = "sse2", target_feature = "sse2"),
+    all(feature = "vsqrt-f64", target_arch = "arm", target_abi = "eabihf"),
+    test
+))]
+pub(crate) fn float_sqrt<T: Primitive>(n: T) -> T {
+    // The width is a constant, so the branch is resolved at compile time
+    if T::zero().count_zeros() <= 32 {
+        T::from(float_sqrt_u32(n.to_u32().unwrap())).unwrap()
+    } else {
+        let n = n.to_u64().unwrap();
+        T::from(correct_u64(n, sqrt_f64(n as f64) as u64)).unwrap()
+    }
+}
+
+/// Find the integer square root of a `u32` from its `f32` square root, for targets that only have
+/// a single precision square root.
+#[cfg(any(
+    feature = "std",
+    feature = "libm",
+    all(feature = "sse2", target_feature = "sse2"),
+    all(feature = "vsqrt", target_arch = "arm", target_abi = "eabihf"),
+    test
+))]
+pub(crate) fn float_sqrt_u32(n: u32) -> u32 {
+    correct_u32(n, sqrt_f32(n as f32) as u32)
+}
+
+// The correction is done with comparisons turned into 0 or 1 rather than with branches, because
+// inputs clustered around squares make a branch on them unpredictable.
+macro_rules! impl_correct {
+    ($(#[$attr:meta])* $name:ident: $type:ty) => {
+        /// Correct an estimate that is within 1 of the root of `n` to the root.
+        $(#[$attr])*
+        fn $name(n: $type, estimate: $type) -> $type {
+            const HALF_BITS: u32 = <$type>::BITS / 2;
+
+            // The estimate is at most 2^HALF_BITS, whose square wraps to 0, so that overshoots too
+            let overshoots = (estimate >> HALF_BITS != 0) | (estimate.wrapping_mul(estimate) > n);
+            let root = estimate - <$type>::from(overshoots);
+            // The root is now the floor or one below it, and below it if the remainder is big
+            // enough for the next square to fit
+            let undershoots = n - root * root > 2 * root;
+            root + <$type>::from(undershoots)
+        }
+    };
+}
+
+impl_correct! {
+    #[cfg(any(feature = "std", feature = "libm", all(feature = "sse2", target_feature = "sse2"), all(feature = "vsqrt", target_arch = "arm", target_abi = "eabihf"), test))]
+    correct_u32: u32
+}
+
+impl_correct! {
+    #[cfg(any(feature = "std", feature = "libm", all(feature = "sse2", target_feature = "sse2"), all(feature = "vsqrt-f64", target_arch = "arm", target_abi = "eabihf"), test))]
+    correct_u64: u64
+}
+
+/// The floating point square roots, from `std` if it is available.
+#[cfg(all(
+    not(all(feature = "sse2", target_feature = "sse2")),
+    not(all(feature = "vsqrt", target_arch = "arm", target_abi = "eabihf")),
+    any(feature = "std", all(test, not(feature = "libm")))
+))]
+fn sqrt_f32(x: f32) -> f32 {
+    x.sqrt()
+}
+
+#[cfg(all(
+    not(all(feature = "sse2", target_feature = "sse2")),
+    not(all(feature = "vsqrt-f64", target_arch = "arm", target_abi = "eabihf")),
+    any(feature = "std", all(test, not(feature = "libm")))
+))]
+fn sqrt_f64(x: f64) -> f64 {
+    x.sqrt()
+}
+
+/// The floating point square roots, from `libm` for targets without `std`.
+#[cfg(all(
+    not(all(feature = "sse2", target_feature = "sse2")),
+    not(all(feature = "vsqrt", target_arch = "arm", target_abi = "eabihf")),
+    feature = "libm",
+    not(feature = "std")
+))]
+fn sqrt_f32(x: f32) -> f32 {
+    libm::sqrtf(x)
+}
+
+#[cfg(all(
+    not(all(feature = "sse2", target_feature = "sse2")),
+    not(all(feature = "vsqrt-f64", target_arch = "arm", target_abi = "eabihf")),
+    feature = "libm",
+    not(feature = "std")
+))]
+fn sqrt_f64(x: f64) -> f64 {
+    libm::sqrt(x)
+}
+
+/// The floating point square roots, from the SSE2 square root instructions. SSE2 is part of the
+/// x86-64 baseline, so these need neither `std` nor runtime detection there, and on 32 bit x86
+/// they are used when the target enables SSE2, as the `i686` targets do.
+#[cfg(all(feature = "sse2", target_feature = "sse2"))]
+fn sqrt_f32(x: f32) -> f32 {
+    unsafe { arch::_mm_cvtss_f32(arch::_mm_sqrt_ss(arch::_mm_set_ss(x))) }
+}
+
+#[cfg(all(feature = "sse2", target_feature = "sse2"))]
+fn sqrt_f64(x: f64) -> f64 {
+    unsafe {
+        let x = arch::_mm_set_sd(x);
+        arch::_mm_cvtsd_f64(arch::_mm_sqrt_sd(x, x))
+    }
+}
+
+/// The floating point square roots, from the VFP square root instructions of hard float ARM
+/// targets such as the Cortex-M4F and M7. Rust only allows the single precision registers as
+/// `asm!` operands when the FPU also has double precision, so the operands are passed in general
+/// purpose registers and moved through `s0` or `d0`, which are saved and restored around the root.
+#[cfg(all(feature = "vsqrt", target_arch = "arm", target_abi = "eabihf"))]
+fn sqrt_f32(x: f32) -> f32 {
+    let root: u32;
+    unsafe {
+        core::arch::asm!(
+            "vpush {{s0}}",
+            "vmov s0, {x}",
+            "vsqrt.f32 s0, s0",
+            "vmov {root}, s0",
+            "vpop {{s0}}",
+            x = in(reg) x.to_bits(),
+            root = lateout(reg) root,
+            options(pure, nomem, preserves_flags)
+        );
+    }
+    f32::from_bits(root)
+}
+
+/// `vsqrt.f64` needs a double precision FPU, such as that of a Cortex-M7 built with
+/// `-C target-cpu=cortex-m7`, so it is only used with the `vsqrt-f64` feature.
+#[cfg(all(feature = "vsqrt-f64", target_arch = "arm", target_abi = "eabihf"))]
+fn sqrt_f64(x: f64) -> f64 {
+    let bits = x.to_bits();
+    let (low, high): (u32, u32);
+    unsafe {
+        core::arch::asm!(
+            "vpush {{d0}}",
+            "vmov d0, {low}, {high}",
+            "vsqrt.f64 d0, d0",
+            "vmov {low}, {high}, d0",
+            "vpop {{d0}}",
+            low = inout(reg) bits as u32 => low,
+            high = inout(reg) (bits >> 32) as u32 => high,
+            options(pure, nomem, preserves_flags)
+        );
+    }
+    f64::from_bits(u64::from(high) << 32 | u64::from(low))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{correct_u32, correct_u64, float_sqrt, isqrt_f64};
+    use FloatSqrtError;
+    use IntegerSquareRoot;
+
+    #[test]
+    fn exact_integers() {
+        for n in 0..=u16::MAX as u64 {
+            assert_eq!(isqrt_f64(n as f64), Ok(n.integer_sqrt()), "in {}", n);
+        }
+        // Squares above 2^53 with few enough significant bits for a float to hold exactly, and
+        // the floats just below them
+        for shift in 27..64 {
+            let root = (1u64 << shift) + (1 << (shift - 20));
+            let square = u128::from(root) * u128::from(root);
+            assert_eq!(isqrt_f64(square as f64), Ok(root), "in {}", square);
+            if shift <= 52 {
+                let below = square - (1 << shift);
+                assert_eq!(isqrt_f64(below as f64), Ok(root - 1), "in {}", below);
+            }
+        }
+        assert_eq!(isqrt_f64(-0.0), Ok(0));
+        assert_eq!(isqrt_f64(u128::MAX as f64 / 4.0), Ok(1 << 63));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(isqrt_f64(0.5), Err(FloatSqrtError::NotInteger));
+        assert_eq!(isqrt_f64(f64::NAN), Err(FloatSqrtError::NotInteger));
+        assert_eq!(isqrt_f64(-1.0), Err(FloatSqrtError::Negative));
+        assert_eq!(isqrt_f64(f64::NEG_INFINITY), Err(FloatSqrtError::Negative));
+        assert_eq!(isqrt_f64(u128::MAX as f64), Err(FloatSqrtError::TooLarge));
+        assert_eq!(isqrt_f64(f64::INFINITY), Err(FloatSqrtError::TooLarge));
+        assert_eq!(isqrt_f64(f64::MAX), Err(FloatSqrtError::TooLarge));
+    }
+
+    #[test]
+    fn corrects_any_close_estimate() {
+        // Whatever the float backend, the correction must give the root from any estimate within
+        // 1 of it, up to the largest the float root can give
+        macro_rules! check {
+            ($correct:ident: $type:ty, $roots:expr) => {
+                for root in $roots {
+                    let limit: $type = 1 << (<$type>::BITS / 2);
+                    let square = root * root;
+                    let neighbours = [square.wrapping_sub(1), square, square + 2 * root];
+                    for (i, &n) in neighbours.iter().enumerate() {
+                        if root == 0 && i == 0 {
+                            continue;
+                        }
+                        let floor = if i == 0 { root - 1 } else { root };
+                        for estimate in floor.saturating_sub(1)..=(floor + 1).min(limit) {
+                            assert_eq!($correct(n, estimate), floor, "in {} from {}", n, estimate);
+                        }
+                    }
+                }
+            };
+        }
+        check!(correct_u32: u32, 0..=u32::from(u16::MAX));
+        check!(
+            correct_u64: u64,
+            (0..=u64::from(u32::MAX)).rev().step_by(997).chain(0..1 << 12)
+        );
+        assert_eq!(correct_u64(u64::MAX, 1 << 32), u64::from(u32::MAX));
+        assert_eq!(correct_u32(u32::MAX, 1 << 16), u32::from(u16::MAX));
+    }
+
+    #[test]
+    fn float_sqrt_corrects() {
+        for n in 0..=u16::MAX {
+            assert_eq!(float_sqrt(n), n.integer_sqrt(), "in {}", n);
+        }
+        // Squares and their neighbours are where rounding changes the floor, and above 2^53 the
+        // conversion to `f64` rounds them together
+        for root in (0..=u64::from(u32::MAX))
+            .rev()
+            .step_by(9_973)
+            .chain(0..1 << 12)
+        {
+            let square = root * root;
+            assert_eq!(float_sqrt(square), root, "in {}", square);
+            if root > 0 {
+                assert_eq!(float_sqrt(square - 1), root - 1, "in {}", square - 1);
+                assert_eq!(float_sqrt(square + 1), root, "in {}", square + 1);
+            }
+            assert_eq!(
+                float_sqrt(square + 2 * root),
+                root,
+                "in {}",
+                square + 2 * root
+            );
+        }
+        for delta in 0..1 << 12 {
+            let n = u64::MAX - delta;
+            assert_eq!(float_sqrt(n), u64::from(u32::MAX), "in {}", n);
+            let half = (n >> 1) as i64;
+            assert_eq!(float_sqrt(half), half.integer_sqrt(), "in {}", half);
+        }
+        // `f32` rounds 32 bit numbers together in the same way above 2^24
+        for root in 1..=u32::from(u16::MAX) {
+            let square = root * root;
+            assert_eq!(float_sqrt(square), root, "in {}", square);
+            assert_eq!(float_sqrt(square - 1), root - 1, "in {}", square - 1);
+            assert_eq!(
+                float_sqrt(square + 2 * root),
+                root,
+                "in {}",
+                square + 2 * root
+            );
+        }
+        for delta in 0..1 << 12 {
+            let n = u32::MAX - delta;
+            assert_eq!(float_sqrt(n), u32::from(u16::MAX), "in {}", n);
+            let half = (n >> 1) as i32;
+            assert_eq!(float_sqrt(half), 46_340, "in {}", half);
+        }
+        assert_eq!(float_sqrt(u32::MAX), u32::from(u16::MAX));
+        assert_eq!(float_sqrt(i32::MAX), 46_340);
+        assert_eq!(float_sqrt(usize::MAX), usize::MAX.integer_sqrt());
+    }
+}
@@ -0,0 +1,272 @@
+use primitive::Primitive;
+use wide::Widen;
+use {IntegerCubeRoot, IntegerNthRoot, IntegerSquareRoot};
+
+/// Find the integer square root of `n`.
+///
+/// This is [`IntegerSquareRoot::integer_sqrt`] as a free function.
+///
+/// # Panics
+///
+/// For negative numbers (`i` family) this function will panic on negative input
+///
+/// [`IntegerSquareRoot::integer_sqrt`]: ./trait.IntegerSquareRoot.html#method.integer_sqrt
+#[inline]
+pub fn isqrt<T: IntegerSquareRoot>(n: T) -> T {
+    n.integer_sqrt()
+}
+
+/// Find the integer square root of `n`, returning `None` if it is negative.
+///
+/// This is [`IntegerSquareRoot::integer_sqrt_checked`] as a free function.
+///
+/// [`IntegerSquareRoot::integer_sqrt_checked`]: ./trait.IntegerSquareRoot.html#method.integer_sqrt_checked
+#[inline]
+pub fn isqrt_checked<T: IntegerSquareRoot>(n: T) -> Option<T> {
+    n.integer_sqrt_checked()
+}
+
+/// Find the integer cube root of `n`.
+///
+/// This is [`IntegerCubeRoot::integer_cbrt`] as a free function.
+///
+/// [`IntegerCubeRoot::integer_cbrt`]: ./trait.IntegerCubeRoot.html#method.integer_cbrt
+#[inline]
+pub fn icbrt<T: IntegerCubeRoot>(n: T) -> T {
+    n.integer_cbrt()
+}
+
+/// Find the integer cube root of `n`.
+///
+/// This is [`IntegerCubeRoot::integer_cbrt_checked`] as a free function.
+///
+/// [`IntegerCubeRoot::integer_cbrt_checked`]: ./trait.IntegerCubeRoot.html#method.integer_cbrt_checked
+#[inline]
+pub fn icbrt_checked<T: IntegerCubeRoot>(n: T) -> Option<T> {
+    n.integer_cbrt_checked()
+}
+
+/// Find the integer `k`th root of `n`.
+///
+/// This is [`IntegerNthRoot::nth_root`] as a free function.
+///
+/// # Panics
+///
+/// If `k` is zero, or for negative numbers (`i` family) when `k` is even.
+///
+/// [`IntegerNthRoot::nth_root`]: ./trait.IntegerNthRoot.html#method.nth_root
+#[inline]
+pub fn iroot<T: IntegerNthRoot>(n: T, k: u32) -> T {
+    n.nth_root(k)
+}
+
+/// Find the integer `k`th root of `n`, returning `None` if `k` is zero or if `n` is negative and
+/// `k` is even.
+///
+/// This is [`IntegerNthRoot::nth_root_checked`] as a free function.
+///
+/// [`IntegerNthRoot::nth_root_checked`]: ./trait.IntegerNthRoot.html#method.nth_root_checked
+#[inline]
+pub fn iroot_checked<T: IntegerNthRoot>(n: T, k: u32) -> Option<T> {
+    n.nth_root_checked(k)
+}
+
+/// Find the integer geometric mean of `a` and `b`, i.e. the integer square root of `a * b`.
+///
+/// The product is computed at twice the width of `T`, so unlike `isqrt(a * b)` this never
+/// overflows. It is implemented for the unsigned types (`u` family).
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::geometric_mean;
+///
+/// # fn main() {
+/// assert_eq!(geometric_mean(4u8, 9), 6);
+/// assert_eq!(geometric_mean(u64::MAX, u64::MAX), u64::MAX);
+/// # }
+/// ```
+#[inline]
+pub fn geometric_mean<T: Widen>(a: T, b: T) -> T {
+    let (high, low) = a.wide_mul(b);
+    T::wide_sqrt(high, low)
+}
+
+/// Find the integer square root of `a * b`, computing the product at twice the width of `T` so
+/// that it never overflows.
+///
+/// This is the same as [`geometric_mean`], under the name that reads better when the product is
+/// what matters.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::sqrt_of_product;
+///
+/// # fn main() {
+/// assert_eq!(sqrt_of_product(1u128 << 127, 1 << 65), 1 << 96);
+/// # }
+/// ```
+///
+/// [`geometric_mean`]: ./fn.geometric_mean.html
+#[inline]
+pub fn sqrt_of_product<T: Widen>(a: T, b: T) -> T {
+    geometric_mean(a, b)
+}
+
+/// Check that `root` is the integer square root of `n`, i.e. that
+/// `root * root <= n < (root + 1) * (root + 1)`, without overflowing.
+///
+/// This is always `false` if `root` or `n` is negative.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::is_integer_sqrt_of;
+///
+/// # fn main() {
+/// assert!(is_integer_sqrt_of(4u8, 24));
+/// assert!(!is_integer_sqrt_of(4u8, 25));
+/// assert!(is_integer_sqrt_of(15u8, u8::MAX));
+/// # }
+/// ```
+pub fn is_integer_sqrt_of<T: Primitive>(root: T, n: T) -> bool {
+    if root < T::zero() {
+        return false;
+    }
+    // n < (root + 1)^2 is the same as n - root^2 <= 2 * root, which cannot overflow once
+    // root^2 is known to fit.
+    match root.checked_mul(&root) {
+        Some(square) if square <= n => n - square <= root + root,
+        _ => false,
+    }
+}
+
+/// Assert that the first argument is the integer square root of the second, using
+/// [`is_integer_sqrt_of`].
+///
+/// ```
+/// #[macro_use]
+/// extern crate integer_sqrt;
+///
+/// # fn main() {
+/// assert_isqrt!(11u32, 140);
+/// assert_isqrt!(11u32, 143, "with a message {}", 1);
+/// # }
+/// ```
+///
+/// [`is_integer_sqrt_of`]: ./fn.is_integer_sqrt_of.html
+#[macro_export]
+macro_rules! assert_isqrt {
+    ($root:expr, $n:expr $(,)*) => {
+        match (&$root, &$n) {
+            (root, n) => assert!(
+                $crate::is_integer_sqrt_of(*root, *n),
+                "{} is not the integer square root of {}",
+                root,
+                n
+            ),
+        }
+    };
+    ($root:expr, $n:expr, $($arg:tt)+) => {
+        match (&$root, &$n) {
+            (root, n) => assert!($crate::is_integer_sqrt_of(*root, *n), $($arg)+),
+        }
+    };
+}
+
+/// Like [`assert_isqrt!`], but only checked in debug builds.
+///
+/// ```
+/// extern crate integer_sqrt;
+///
+/// # fn main() {
+/// integer_sqrt::debug_assert_isqrt!(3u32, 15);
+/// # }
+/// ```
+///
+/// [`assert_isqrt!`]: ./macro.assert_isqrt.html
+#[macro_export]
+macro_rules! debug_assert_isqrt {
+    ($($arg:tt)*) => {
+        if cfg!(debug_assertions) {
+            $crate::assert_isqrt!($($arg)*);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        geometric_mean, icbrt, icbrt_checked, iroot, iroot_checked, is_integer_sqrt_of, isqrt,
+        isqrt_checked, sqrt_of_product,
+    };
+    use IntegerSquareRoot;
+
+    #[test]
+    fn free_functions() {
+        assert_eq!(isqrt(99u32), 9);
+        assert_eq!(isqrt_checked(-99i32), None);
+        assert_eq!(icbrt(-30i64), -3);
+        assert_eq!(icbrt_checked(30u8), Some(3));
+        assert_eq!(iroot(1u128 << 100, 10), 1 << 10);
+        assert_eq!(iroot_checked(-16i16, 4), None);
+        assert_eq!(iroot_checked(16i16, 0), None);
+    }
+
+    #[test]
+    fn geometric_mean_test() {
+        for a in 0..=u8::MAX {
+            for b in 0..=u8::MAX {
+                let expected = isqrt(u16::from(a) * u16::from(b)) as u8;
+                assert_eq!(geometric_mean(a, b), expected, "{} {}", a, b);
+            }
+        }
+        assert_eq!(geometric_mean(u32::MAX, 1), 65535);
+        assert_eq!(geometric_mean(u64::MAX, 4), u64::MAX >> 31);
+        assert_eq!(geometric_mean(usize::MAX, usize::MAX), usize::MAX);
+        assert_eq!(geometric_mean(u128::MAX, u128::MAX), u128::MAX);
+        assert_eq!(geometric_mean(u128::MAX, u128::MAX - 1), u128::MAX - 1);
+        assert_eq!(geometric_mean(1u128 << 100, 1 << 60), 1 << 80);
+        assert_eq!(geometric_mean(u128::MAX, 0), 0);
+    }
+
+    #[test]
+    fn is_integer_sqrt_of_test() {
+        for n in 0..=u8::MAX {
+            for root in 0..=u8::MAX {
+                let (n16, root16) = (u16::from(n), u16::from(root));
+                let expected = root16 * root16 <= n16 && (root16 + 1) * (root16 + 1) > n16;
+                assert_eq!(is_integer_sqrt_of(root, n), expected, "{} {}", root, n);
+            }
+        }
+        for n in i8::MIN..=i8::MAX {
+            for root in i8::MIN..=i8::MAX {
+                let expected = root >= 0 && n >= 0 && root == n.integer_sqrt();
+                assert_eq!(is_integer_sqrt_of(root, n), expected, "{} {}", root, n);
+            }
+        }
+        assert!(is_integer_sqrt_of(u128::MAX.integer_sqrt(), u128::MAX));
+        assert!(!is_integer_sqrt_of(u128::MAX, u128::MAX));
+        assert_isqrt!(u64::MAX.integer_sqrt(), u64::MAX);
+        debug_assert_isqrt!(3i32, 15, "custom {}", "message");
+    }
+
+    #[test]
+    #[should_panic(expected = "4 is not the integer square root of 25")]
+    fn assert_isqrt_panics() {
+        assert_isqrt!(4, 25);
+    }
+
+    #[test]
+    fn sqrt_of_product_test() {
+        for a in (0..=u16::MAX).step_by(97) {
+            for b in (0..=u16::MAX).step_by(89) {
+                let expected = isqrt(u32::from(a) * u32::from(b)) as u16;
+                assert_eq!(sqrt_of_product(a, b), expected, "{} {}", a, b);
+            }
+        }
+        assert_eq!(sqrt_of_product(u128::MAX, 4), (u64::MAX as u128) << 1 | 1);
+        assert_eq!(
+            sqrt_of_product(u128::MAX, u128::MAX / 4),
+            170141183460469231731687303715884105726
+        );
+    }
+}
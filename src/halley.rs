@@ -0,0 +1,75 @@
+use newton::first_guess;
+use primitive::Primitive;
+
+/// Find the integer square root of non-negative `n` by Halley's method.
+///
+/// Each guess `x` is improved to `x - 2x (x - n / x) / (3x + n / x)`, which triples the number of
+/// correct bits where Newton's method doubles them, at the cost of a multiplication and a second
+/// division per step. Starting from a power of two no smaller than the root, every guess stays
+/// at or above the root: truncating `n / x` adds less than one to the step, and stepping by at
+/// least one is safe while `x * x > n`. The guesses stop at the first `x` with `x <= n / x`.
+///
+/// While the guess is far from the root, `2x (x - n / x)` can overflow for the widest types, and
+/// a Newton step is taken instead.
+pub(crate) fn halley_sqrt<T: Primitive>(n: T) -> T {
+    let mut x = first_guess(n);
+    if x == T::zero() {
+        return x;
+    }
+    let two = T::one() + T::one();
+    loop {
+        let q = n / x;
+        if q >= x {
+            return x;
+        }
+        // x is at most 2^ceil(bits / 2) and q is less than it, so neither of these overflow
+        let (twice, denominator) = (x + x, x + x + x + q);
+        x = match (x - q).checked_mul(&twice) {
+            Some(numerator) => x - (numerator / denominator).max(T::one()),
+            None => (x + q) / two,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::halley_sqrt;
+    use newton::newton_sqrt;
+
+    #[test]
+    fn halley() {
+        for n in 0..=u16::MAX {
+            assert_eq!(halley_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for n in 0..=i16::MAX {
+            assert_eq!(halley_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for n in 0..=u8::MAX {
+            assert_eq!(halley_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for n in 0..=i8::MAX {
+            assert_eq!(halley_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for shift in 0..128 {
+            for n in [1u128 << shift, (1 << shift) - 1, u128::MAX >> shift] {
+                assert_eq!(halley_sqrt(n), newton_sqrt(n), "in {}", n);
+                let n = (n >> 1) as i128;
+                assert_eq!(halley_sqrt(n), newton_sqrt(n), "in {}", n);
+                let n = n as u64;
+                assert_eq!(halley_sqrt(n), newton_sqrt(n), "in {}", n);
+            }
+        }
+        for root in (0..1u64 << 12).map(|i| u64::MAX - (i << 40)) {
+            let square = u128::from(root) * u128::from(root);
+            for n in [square - 1, square, square + 2 * u128::from(root)] {
+                assert_eq!(halley_sqrt(n), u128::from(root) - (n < square) as u128);
+            }
+        }
+        for root in (0..1u32 << 12).map(|i| u32::MAX - (i << 20)) {
+            let square = u64::from(root) * u64::from(root);
+            for n in [square - 1, square, square + 2 * u64::from(root)] {
+                assert_eq!(halley_sqrt(n), u64::from(root) - (n < square) as u64);
+            }
+        }
+    }
+}
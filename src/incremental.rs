@@ -0,0 +1,134 @@
+use primitive::Primitive;
+use IntegerSquareRoot;
+
+/// Tracks the integer square root of a number as it increases.
+///
+/// Each step only compares the number with the next perfect square, and the next square is only
+/// recomputed when the root changes (once every `2 * root + 1` increments), so following a
+/// number upwards costs amortized O(1) per step rather than a full root each time.
+///
+/// # Example
+///
+/// ```
+/// # extern crate integer_sqrt;
+/// use integer_sqrt::IncrementalSqrt;
+///
+/// # fn main() {
+/// let mut tracker = IncrementalSqrt::new(7u32);
+/// assert_eq!(tracker.root(), 2);
+/// assert_eq!(tracker.increment(), 2);
+/// assert_eq!(tracker.increment(), 3);
+/// assert_eq!(tracker.advance(7), 4);
+/// assert_eq!(tracker.n(), 16);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalSqrt<T> {
+    n: T,
+    root: T,
+    /// `(root + 1)^2`, or `None` if that does not fit in `T`.
+    next_square: Option<T>,
+}
+
+impl<T: Primitive> IncrementalSqrt<T> {
+    /// Start tracking the root of `n`.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    pub fn new(n: T) -> Self {
+        let root = n.integer_sqrt();
+        IncrementalSqrt {
+            n,
+            root,
+            next_square: Self::square_after(root),
+        }
+    }
+
+    /// The number currently being tracked.
+    pub fn n(&self) -> T {
+        self.n
+    }
+
+    /// The integer square root of the number currently being tracked.
+    pub fn root(&self) -> T {
+        self.root
+    }
+
+    /// Add one to the number, returning its new root.
+    ///
+    /// # Panics
+    ///
+    /// If the number would overflow `T`.
+    pub fn increment(&mut self) -> T {
+        self.advance(T::one())
+    }
+
+    /// Add `delta` to the number, returning its new root.
+    ///
+    /// # Panics
+    ///
+    /// If `delta` is negative, or if the number would overflow `T`.
+    pub fn advance(&mut self, delta: T) -> T {
+        assert!(delta >= T::zero(), "IncrementalSqrt can only move forwards");
+        self.n = self
+            .n
+            .checked_add(&delta)
+            .expect("IncrementalSqrt number overflowed");
+        match self.next_square {
+            Some(next_square) if self.n >= next_square => {
+                self.root = self.n.integer_sqrt_with_hint(self.root + T::one());
+                self.next_square = Self::square_after(self.root);
+            }
+            _ => {}
+        }
+        self.root
+    }
+
+    fn square_after(root: T) -> Option<T> {
+        let next = root + T::one();
+        next.checked_mul(&next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IncrementalSqrt;
+    use IntegerSquareRoot;
+
+    #[test]
+    fn increment() {
+        let mut tracker = IncrementalSqrt::new(0u16);
+        assert_eq!(tracker.root(), 0);
+        while tracker.n() < u16::MAX {
+            let root = tracker.increment();
+            assert_eq!(root, tracker.n().integer_sqrt(), "in {}", tracker.n());
+        }
+        assert_eq!(tracker.root(), 255);
+    }
+
+    #[test]
+    fn advance() {
+        let mut tracker = IncrementalSqrt::new(5i64);
+        let mut delta = 0;
+        while delta < i64::MAX / 8 {
+            let root = tracker.advance(delta);
+            assert_eq!(root, tracker.n().integer_sqrt(), "in {}", tracker.n());
+            delta = delta * 3 + 1;
+        }
+        let root = tracker.advance(i64::MAX - tracker.n());
+        assert_eq!(root, i64::max_sqrt());
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflow() {
+        IncrementalSqrt::new(u8::MAX).increment();
+    }
+
+    #[test]
+    #[should_panic]
+    fn backwards() {
+        IncrementalSqrt::new(10i8).advance(-1);
+    }
+}
@@ -0,0 +1,149 @@
+use core::iter::FusedIterator;
+
+use IntegerSquareRoot;
+
+/// An extension trait for iterators of integers, to take the integer square root of each item.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::IntegerSqrtIteratorExt;
+///
+/// # fn main() {
+/// let roots: Vec<u32> = [1u32, 10, 100, 1000].iter().cloned().integer_sqrts().collect();
+/// assert_eq!(roots, [1, 3, 10, 31]);
+///
+/// let roots: Vec<_> = (-1..2i8).checked_integer_sqrts().collect();
+/// assert_eq!(roots, [None, Some(0), Some(1)]);
+/// # }
+/// ```
+pub trait IntegerSqrtIteratorExt: Iterator + Sized
+where
+    Self::Item: IntegerSquareRoot,
+{
+    /// Iterate over the integer square roots of the items.
+    ///
+    /// The iterator panics when it reaches a negative item (`i` family).
+    fn integer_sqrts(self) -> IntegerSqrts<Self> {
+        IntegerSqrts { iter: self }
+    }
+
+    /// Iterate over the integer square roots of the items, yielding `None` for the negative ones
+    /// (this can never happen for unsigned types).
+    fn checked_integer_sqrts(self) -> CheckedIntegerSqrts<Self> {
+        CheckedIntegerSqrts { iter: self }
+    }
+}
+
+impl<I> IntegerSqrtIteratorExt for I
+where
+    I: Iterator,
+    I::Item: IntegerSquareRoot,
+{
+}
+
+// Both adapters forward everything to the inner iterator, only rooting the items it yields.
+macro_rules! impl_adapter {
+    ($(#[$attr:meta])* $name:ident, $method:ident, $output:ty) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone)]
+        pub struct $name<I> {
+            iter: I,
+        }
+
+        impl<I> Iterator for $name<I>
+        where
+            I: Iterator,
+            I::Item: IntegerSquareRoot,
+        {
+            type Item = $output;
+
+            #[inline]
+            fn next(&mut self) -> Option<$output> {
+                self.iter.next().map(|n| n.$method())
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iter.size_hint()
+            }
+
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<$output> {
+                self.iter.nth(n).map(|n| n.$method())
+            }
+        }
+
+        impl<I> DoubleEndedIterator for $name<I>
+        where
+            I: DoubleEndedIterator,
+            I::Item: IntegerSquareRoot,
+        {
+            #[inline]
+            fn next_back(&mut self) -> Option<$output> {
+                self.iter.next_back().map(|n| n.$method())
+            }
+        }
+
+        impl<I> ExactSizeIterator for $name<I>
+        where
+            I: ExactSizeIterator,
+            I::Item: IntegerSquareRoot,
+        {
+        }
+
+        impl<I> FusedIterator for $name<I>
+        where
+            I: FusedIterator,
+            I::Item: IntegerSquareRoot,
+        {
+        }
+    };
+}
+
+impl_adapter! {
+    /// An iterator over the integer square roots of the items of another, created by
+    /// [`IntegerSqrtIteratorExt::integer_sqrts`].
+    ///
+    /// [`IntegerSqrtIteratorExt::integer_sqrts`]: ./trait.IntegerSqrtIteratorExt.html#method.integer_sqrts
+    IntegerSqrts, integer_sqrt, I::Item
+}
+
+impl_adapter! {
+    /// An iterator over the checked integer square roots of the items of another, created by
+    /// [`IntegerSqrtIteratorExt::checked_integer_sqrts`].
+    ///
+    /// [`IntegerSqrtIteratorExt::checked_integer_sqrts`]: ./trait.IntegerSqrtIteratorExt.html#method.checked_integer_sqrts
+    CheckedIntegerSqrts, integer_sqrt_checked, Option<I::Item>
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntegerSqrtIteratorExt;
+    use IntegerSquareRoot;
+
+    #[test]
+    fn integer_sqrts() {
+        assert!((0..=u16::MAX)
+            .integer_sqrts()
+            .eq((0..=u16::MAX).map(|n| n.integer_sqrt())));
+        let mut roots = (90..=100u8).integer_sqrts();
+        assert_eq!(roots.len(), 11);
+        assert_eq!(roots.next_back(), Some(10));
+        assert_eq!(roots.nth(1), Some(9));
+        assert_eq!(roots.size_hint(), (8, Some(8)));
+        assert_eq!(roots.next_back(), Some(9));
+    }
+
+    #[test]
+    fn checked_integer_sqrts() {
+        let roots = [4i64, -4, 9].iter().cloned().checked_integer_sqrts();
+        assert!(roots.eq([Some(2), None, Some(3)].iter().cloned()));
+        assert_eq!((-5..5i32).checked_integer_sqrts().flatten().sum::<i32>(), 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative() {
+        (-1..1i32).integer_sqrts().for_each(drop);
+    }
+}
@@ -0,0 +1,81 @@
+use core::iter::FusedIterator;
+
+use primitive::Primitive;
+use IntegerSquareRoot;
+
+/// Iterate over `n`, its integer square root, the root of that, and so on, down to 1.
+///
+/// The sequence ends at the first 0 or 1, which is reached after about `log2(log2(n))` roots.
+/// Its length minus one is the number of square roots needed to bring `n` down to 1.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::iterated_sqrt;
+///
+/// # fn main() {
+/// let roots: Vec<u32> = iterated_sqrt(70_000u32).collect();
+/// assert_eq!(roots, [70_000, 264, 16, 4, 2, 1]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// For negative numbers (`i` family) this function will panic on negative input
+pub fn iterated_sqrt<T: Primitive>(n: T) -> IteratedSqrt<T> {
+    iterated_sqrt_checked(n).expect("cannot calculate square root of negative number")
+}
+
+/// Iterate over `n`, its integer square root, the root of that, and so on, down to 1, returning
+/// `None` if `n` is negative (this can never happen for unsigned types).
+pub fn iterated_sqrt_checked<T: Primitive>(n: T) -> Option<IteratedSqrt<T>> {
+    if n < T::zero() {
+        None
+    } else {
+        Some(IteratedSqrt { next: Some(n) })
+    }
+}
+
+/// An iterator over repeated integer square roots, created by [`iterated_sqrt`].
+///
+/// [`iterated_sqrt`]: ./fn.iterated_sqrt.html
+#[derive(Debug, Clone)]
+pub struct IteratedSqrt<T> {
+    /// The next number to yield, or `None` once 0 or 1 has been yielded
+    next: Option<T>,
+}
+
+impl<T: Primitive> Iterator for IteratedSqrt<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let n = self.next?;
+        self.next = if n > T::one() {
+            Some(n.integer_sqrt())
+        } else {
+            None
+        };
+        Some(n)
+    }
+}
+
+impl<T: Primitive> FusedIterator for IteratedSqrt<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{iterated_sqrt, iterated_sqrt_checked};
+
+    #[test]
+    fn iterated() {
+        let max = u128::MAX;
+        let expected = [max, max >> 64, max >> 96, 65_535, 255, 15, 3, 1];
+        assert!(iterated_sqrt(max).eq(expected.iter().cloned()));
+        assert!(iterated_sqrt(0u8).eq([0].iter().cloned()));
+        assert!(iterated_sqrt(1u8).eq([1].iter().cloned()));
+        assert!(iterated_sqrt(3i8).eq([3, 1].iter().cloned()));
+    }
+
+    #[test]
+    fn negative() {
+        assert!(iterated_sqrt_checked(-1i32).is_none());
+    }
+}
@@ -0,0 +1,111 @@
+use IntegerSquareRoot;
+
+// Each root is found from the root of the top half of the number, by the divide and conquer step
+// of Zimmermann's "Karatsuba Square Root". Writing the (normalised) number in quarter-width limbs
+// as `a3 b^3 + a2 b^2 + a1 b + a0`, the root `s'` and remainder `r'` of `a3 b + a2` give the root
+// as `s' b + q`, where `q` is the quotient of `r' b + a1` by `2 s'`, less one if that overshoots.
+//
+// `r' b + a1` can need one bit more than half width, so it is halved and divided by `s'` rather
+// than `2 s'`. The quotient is the same, and the remainder gets back the bit that was shifted out.
+// With that, apart from the shifts at the start and end, everything is done in half-width
+// arithmetic, so a 128 bit root on a 32 bit target only ever needs 32 bit arithmetic and a 64 bit
+// division by a 32 bit number.
+macro_rules! impl_karatsuba {
+    ($(#[$attr:meta])* $name:ident: $type:ty => $half:ty, $quarter:ty) => {
+        $(#[$attr])*
+        pub(crate) fn $name(n: $type) -> $type {
+            const QUARTER_BITS: u32 = <$quarter>::BITS;
+
+            if n == 0 {
+                return 0;
+            }
+            // The step needs one of the top two bits to be set. Scaling by 4^k scales the root by
+            // 2^k, which is shifted back out at the end.
+            let shift = n.leading_zeros() & !1;
+            let n = n << shift;
+
+            let (root, rem) = ((n >> (2 * QUARTER_BITS)) as $half).integer_sqrt_rem();
+            let (a1, a0) = ((n >> QUARTER_BITS) as $quarter, n as $quarter);
+            let halved = (rem << (QUARTER_BITS - 1)) | <$half>::from(a1 >> 1);
+            let (q, r) = (halved / root, halved % root);
+            let u = (r << 1) | <$half>::from(a1 & 1);
+            // The remainder is u b + a0 - q^2, and the root overshoots by one if that is negative.
+            // q is at most b, and when it is b, q^2 wraps to 0 but u b + a0 is only at least b^2
+            // if u >= b. The comparisons are combined without branching, because whether the root
+            // overshoots is unpredictable for inputs clustered around squares.
+            let lhs = (u << QUARTER_BITS) | <$half>::from(a0);
+            let overshoots = (u >> QUARTER_BITS == 0)
+                & ((q >> QUARTER_BITS != 0) | (lhs < q.wrapping_mul(q)));
+            let root = (<$type>::from(root) << QUARTER_BITS) + <$type>::from(q)
+                - <$type>::from(overshoots);
+            root >> (shift / 2)
+        }
+    };
+}
+
+impl_karatsuba! {
+    /// Find the integer square root of a `u128` from the root of its top 64 bits.
+    karatsuba_sqrt_u128: u128 => u64, u32
+}
+
+impl_karatsuba! {
+    /// Find the integer square root of a `u64` from the root of its top 32 bits, which avoids the
+    /// software 64 bit division that Newton's method needs on 32 bit targets.
+    karatsuba_sqrt_u64: u64 => u32, u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{karatsuba_sqrt_u128, karatsuba_sqrt_u64};
+    use newton::newton_sqrt;
+
+    #[test]
+    fn u128_matches_newton() {
+        for n in 0..=u128::from(u16::MAX) {
+            assert_eq!(karatsuba_sqrt_u128(n), newton_sqrt(n), "in {}", n);
+        }
+        for shift in 0..128 {
+            for &n in [1u128 << shift, (1 << shift) - 1, (1 << shift) + 1].iter() {
+                assert_eq!(karatsuba_sqrt_u128(n), newton_sqrt(n), "in {}", n);
+                let n = u128::MAX >> shift;
+                assert_eq!(karatsuba_sqrt_u128(n), newton_sqrt(n), "in {}", n);
+            }
+        }
+        for root in (0..=u128::from(u16::MAX)).map(|root| root << 48) {
+            for &n in [
+                root * root,
+                root * root + 2 * root,
+                (root * root).wrapping_sub(1),
+            ]
+            .iter()
+            {
+                assert_eq!(karatsuba_sqrt_u128(n), newton_sqrt(n), "in {}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn u64_matches_newton() {
+        for n in 0..=u64::from(u16::MAX) {
+            assert_eq!(karatsuba_sqrt_u64(n), newton_sqrt(n), "in {}", n);
+        }
+        for shift in 0..64 {
+            for &n in [1u64 << shift, (1 << shift) - 1, (1 << shift) + 1].iter() {
+                assert_eq!(karatsuba_sqrt_u64(n), newton_sqrt(n), "in {}", n);
+                let n = u64::MAX >> shift;
+                assert_eq!(karatsuba_sqrt_u64(n), newton_sqrt(n), "in {}", n);
+            }
+        }
+        for root in (0..=u64::from(u32::MAX)).step_by(65_521) {
+            for &n in [
+                root * root,
+                root * root + 2 * root,
+                (root * root).wrapping_sub(1),
+            ]
+            .iter()
+            {
+                assert_eq!(karatsuba_sqrt_u64(n), newton_sqrt(n), "in {}", n);
+            }
+        }
+    }
+}
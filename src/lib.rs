@@ -1,6 +1,8 @@
 //!
-//! This module contains the single trait [`IntegerSquareRoot`] and implements it for primitive
-//! integer types.
+//! This module contains the trait [`IntegerSquareRoot`], along with the companion traits
+//! [`IntegerCubeRoot`] and [`IntegerNthRoot`] for other roots and [`PerfectSquares`] for working
+//! with the squares around a number, and implements them for primitive integer types. The
+//! non-zero unsigned types have their own [`NonZeroSquareRoot`] trait, which keeps roots non-zero.
 //!
 //! # Example
 //!
@@ -14,15 +16,203 @@
 //! # }
 //! ```
 //!
+//! All of the traits can be imported at once from the [`prelude`], [`RootsExt`] gathers the
+//! rounding variants of the square, cube and `k`th roots into one trait, and the most common
+//! methods are also available as free functions such as [`isqrt`].
+//!
+//! ```
+//! extern crate integer_sqrt;
+//! use integer_sqrt::prelude::*;
+//!
+//! # fn main() {
+//! assert_eq!(27u8.integer_cbrt(), integer_sqrt::isqrt(9u8));
+//! # }
+//! ```
+//!
 //! [`IntegerSquareRoot`]: ./trait.IntegerSquareRoot.html
+//! [`IntegerCubeRoot`]: ./trait.IntegerCubeRoot.html
+//! [`IntegerNthRoot`]: ./trait.IntegerNthRoot.html
+//! [`PerfectSquares`]: ./trait.PerfectSquares.html
+//! [`NonZeroSquareRoot`]: ./trait.NonZeroSquareRoot.html
+//! [`prelude`]: ./prelude/index.html
+//! [`RootsExt`]: ./trait.RootsExt.html
+//! [`isqrt`]: ./fn.isqrt.html
 #![no_std]
+#![cfg_attr(feature = "portable_simd", feature(portable_simd))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+pub mod algorithms;
+mod array;
+mod binary_search;
+mod bitwise;
+mod cbrt;
+mod cordic;
+#[cfg(feature = "ct")]
+mod ct;
+#[cfg(feature = "alloc")]
+mod digits;
+mod error;
+mod figurate;
+mod fixed;
+mod float;
+mod functions;
+mod halley;
+mod incremental;
+mod iter;
+mod iterated;
+mod karatsuba;
+mod map;
+mod narrow;
+#[cfg(all(
+    target_arch = "aarch64",
+    target_feature = "neon",
+    not(feature = "portable_simd")
+))]
+mod neon;
+mod newton;
+mod non_zero;
+mod norm;
+mod nth_root;
+#[cfg(feature = "rayon")]
+mod par;
+pub mod prelude;
+mod primitive;
+mod roots;
+#[cfg(feature = "portable_simd")]
+mod simd;
+mod slice;
+mod squares;
+mod table;
+#[cfg(all(
+    target_arch = "wasm32",
+    target_feature = "simd128",
+    not(feature = "portable_simd")
+))]
+mod wasm;
+mod wide;
+mod wrappers;
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+mod x86;
+
+pub use array::ArraySquareRoot;
+pub use cbrt::IntegerCubeRoot;
+#[cfg(feature = "ct")]
+pub use ct::{ConstantTimeRoots, ConstantTimeSqrt};
+#[cfg(feature = "alloc")]
+pub use digits::{sqrt_digits, SqrtDigits};
+pub use error::{FloatSqrtError, NegativeSqrtError};
+pub use figurate::FigurateRoots;
+pub use fixed::{
+    integer_rsqrt_scaled, integer_rsqrt_scaled_checked, integer_rsqrt_scaled_saturating,
+    sqrt_decimal_scaled, sqrt_decimal_scaled_checked, sqrt_decimal_scaled_saturating, sqrt_fixed,
+    sqrt_fixed_checked, sqrt_fixed_saturating, sqrt_ratio, sqrt_ratio_checked,
+};
+pub use float::isqrt_f64;
+pub use functions::{
+    geometric_mean, icbrt, icbrt_checked, iroot, iroot_checked, is_integer_sqrt_of, isqrt,
+    isqrt_checked, sqrt_of_product,
+};
+pub use incremental::IncrementalSqrt;
+pub use iter::{CheckedIntegerSqrts, IntegerSqrtIteratorExt, IntegerSqrts};
+pub use iterated::{iterated_sqrt, iterated_sqrt_checked, IteratedSqrt};
+pub use map::MapIntegerSquareRoot;
+pub use narrow::{NarrowingSqrt, SqrtInto};
+pub use newton::{newton_steps, newton_steps_checked, NewtonSteps};
+pub use non_zero::NonZeroSquareRoot;
+pub use norm::{
+    integer_hypot, integer_hypot3, sqrt_of_sum_of_squares, sqrt_of_sum_of_squares_checked,
+};
+pub use nth_root::IntegerNthRoot;
+#[cfg(feature = "rayon")]
+pub use par::{
+    par_integer_sqrt_slice, par_integer_sqrt_slice_checked, ParSqrtCheckedMap, ParSqrtMap,
+    ParallelSqrtIterator,
+};
+pub use roots::RootsExt;
+pub use slice::{
+    integer_sqrt_slice, integer_sqrt_slice_checked, integer_sqrt_slice_in_place,
+    integer_sqrt_slice_in_place_checked, integer_sqrt_slice_uninit,
+    integer_sqrt_slice_uninit_checked, is_perfect_square_slice,
+};
+pub use squares::{
+    count_squares_in, perfect_square_bitmap, range_with_sqrt, sqrt_interval, squares_in_range,
+    PerfectSquares, SquaresInRange,
+};
+pub use table::ConstSqrtTable;
+#[cfg(feature = "alloc")]
+pub use table::SqrtTable;
+pub use wide::{Hypot, Magnitude, Widen};
+
+use primitive::{from_bool, Primitive};
+
+/// How the result of a root should be rounded to an integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Round down, giving the largest `r` such that `r * r <= n`.
+    Floor,
+    /// Round up, giving the smallest `r` such that `r * r >= n`.
+    Ceil,
+    /// Round to the nearest integer.
+    Nearest,
+}
+
+/// The floor and ceiling of a square root, as returned by [`IntegerSquareRoot::sqrt_full`].
+///
+/// [`IntegerSquareRoot::sqrt_full`]: ./trait.IntegerSquareRoot.html#method.sqrt_full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SqrtResult<T> {
+    /// The largest `r` such that `r * r <= n`.
+    pub floor: T,
+    /// The smallest `r` such that `r * r >= n`.
+    pub ceil: T,
+    /// Whether `n` is a perfect square (in which case `floor == ceil`).
+    pub is_exact: bool,
+}
 
 /// A trait implementing integer square root.
+///
+/// The methods take `self` by reference, so they can be passed straight to iterator adapters
+/// over references, without a `copied()` step:
+///
+/// ```
+/// # extern crate integer_sqrt;
+/// use integer_sqrt::IntegerSquareRoot;
+///
+/// # fn main() {
+/// let squares = [1u64, 4, 9, 17];
+/// let roots: Vec<u64> = squares.iter().map(IntegerSquareRoot::integer_sqrt).collect();
+/// assert_eq!(roots, [1, 2, 3, 4]);
+/// # }
+/// ```
+///
+/// Method calls borrow automatically, so `x.integer_sqrt()` works on a value, and in generic code
+/// bounded on `T: IntegerSquareRoot`, without writing `(&x)`. Where a function taking the number
+/// by value is wanted, e.g. to map over an iterator of values, the free functions such as
+/// [`isqrt`] and [`isqrt_checked`] do that:
+///
+/// ```
+/// # extern crate integer_sqrt;
+/// use integer_sqrt::isqrt;
+///
+/// # fn main() {
+/// let roots: Vec<u64> = vec![1u64, 4, 9, 17].into_iter().map(isqrt).collect();
+/// assert_eq!(roots, [1, 2, 3, 4]);
+/// # }
+/// ```
+///
+/// [`isqrt`]: ./fn.isqrt.html
+/// [`isqrt_checked`]: ./fn.isqrt_checked.html
 pub trait IntegerSquareRoot {
     /// Find the integer square root.
     ///
     /// See [Integer_square_root on wikipedia][wiki_article] for more information (and also the
-    /// source of this algorithm)
+    /// source of these algorithms)
     ///
     /// # Panics
     ///
@@ -42,46 +232,737 @@ pub trait IntegerSquareRoot {
     fn integer_sqrt_checked(&self) -> Option<Self>
     where
         Self: Sized;
+
+    /// Find the integer square root, with the same name and behaviour as the standard library's
+    /// `isqrt`.
+    ///
+    /// This is the same as [`integer_sqrt`], for code moving between the two. For the primitive
+    /// types the inherent `isqrt` method takes precedence in method-call syntax, so this one is
+    /// reached through generic code or as `IntegerSquareRoot::isqrt(&n)`.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    ///
+    /// [`integer_sqrt`]: #method.integer_sqrt
+    #[inline]
+    fn isqrt(&self) -> Self
+    where
+        Self: Sized,
+    {
+        self.integer_sqrt()
+    }
+
+    /// Find the integer square root, returning `None` if the number is negative, with the same
+    /// name and behaviour as the standard library's `checked_isqrt`.
+    ///
+    /// This is the same as [`integer_sqrt_checked`].
+    ///
+    /// [`integer_sqrt_checked`]: #method.integer_sqrt_checked
+    #[inline]
+    fn checked_isqrt(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        self.integer_sqrt_checked()
+    }
+
+    /// Find the integer square root, returning 0 if the number is negative (this can never
+    /// happen for unsigned types).
+    ///
+    /// This never panics, for contexts where a panic is worse than a wrong but harmless answer.
+    fn integer_sqrt_or_zero(&self) -> Self
+    where
+        Self: Sized;
+
+    /// Find the number of bits in the integer square root (0 for 0), without computing the root.
+    ///
+    /// If `n` has `b` significant bits then `2^(b-1) <= n < 2^b`, so the root lies in
+    /// `[2^((b-1)/2), 2^(b/2))` and always has exactly `ceil(b / 2)` bits. This only needs
+    /// `leading_zeros`, so is much cheaper than the root itself.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    fn sqrt_bits(&self) -> u32
+    where
+        Self: Sized,
+    {
+        self.sqrt_bits_checked()
+            .expect("cannot calculate square root of negative number")
+    }
+
+    /// Find the number of bits in the integer square root, returning `None` if the number is
+    /// negative (this can never happen for unsigned types).
+    fn sqrt_bits_checked(&self) -> Option<u32>
+    where
+        Self: Sized;
+
+    /// Find a `(lower, upper)` pair bracketing the integer square root, without computing it.
+    ///
+    /// The bounds are the smallest and largest roots with [`sqrt_bits`] bits, so they come from
+    /// `leading_zeros` and a couple of shifts, with no loop, and are within a factor of 2 of each
+    /// other. This is enough for coarse checks, such as culling by distance, that only need to
+    /// know roughly where the root lies. Both bounds are 0 for 0.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    ///
+    /// [`sqrt_bits`]: #method.sqrt_bits
+    fn sqrt_estimate(&self) -> (Self, Self)
+    where
+        Self: Sized,
+    {
+        self.sqrt_estimate_checked()
+            .expect("cannot calculate square root of negative number")
+    }
+
+    /// Find a `(lower, upper)` pair bracketing the integer square root, returning `None` if the
+    /// number is negative (this can never happen for unsigned types).
+    fn sqrt_estimate_checked(&self) -> Option<(Self, Self)>
+    where
+        Self: Sized;
+
+    /// Find the integer square root, starting from an estimate `hint` of the answer.
+    ///
+    /// The hint is corrected a step at a time by comparing squares, which is much cheaper than
+    /// computing the root from scratch when it is off by no more than a few, e.g. when scanning
+    /// consecutive numbers and reusing the previous root. A hint that is further off falls back
+    /// to the normal algorithm, so the result is always exact whatever the hint.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    fn integer_sqrt_with_hint(&self, hint: Self) -> Self
+    where
+        Self: Sized,
+    {
+        self.integer_sqrt_with_hint_checked(hint)
+            .expect("cannot calculate square root of negative number")
+    }
+
+    /// Find the integer square root starting from an estimate `hint`, returning `None` if the
+    /// number is negative (this can never happen for unsigned types).
+    fn integer_sqrt_with_hint_checked(&self, hint: Self) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Find the integer square root, without checking that the number is non-negative.
+    ///
+    /// The negative case is marked unreachable, so the compiler is free to remove the sign test
+    /// and anything that only exists to handle it. Prefer [`integer_sqrt`] unless that test has
+    /// been measured to matter.
+    ///
+    /// # Safety
+    ///
+    /// The number must not be negative (this can never happen for unsigned types). Calling this
+    /// on a negative number is undefined behaviour.
+    ///
+    /// [`integer_sqrt`]: #method.integer_sqrt
+    #[inline]
+    unsafe fn integer_sqrt_unchecked(&self) -> Self
+    where
+        Self: Sized,
+    {
+        match self.integer_sqrt_checked() {
+            Some(root) => root,
+            None => core::hint::unreachable_unchecked(),
+        }
+    }
+
+    /// Find the integer square root, returning an error carrying the number if it is negative
+    /// (this can never happen for unsigned types).
+    ///
+    /// This is the same as [`integer_sqrt_checked`], but the error composes with `?` in functions
+    /// returning richer error types.
+    ///
+    /// [`integer_sqrt_checked`]: #method.integer_sqrt_checked
+    fn try_integer_sqrt(&self) -> Result<Self, NegativeSqrtError<Self>>
+    where
+        Self: Sized + Clone,
+    {
+        self.integer_sqrt_checked()
+            .ok_or_else(|| NegativeSqrtError::new(self.clone()))
+    }
+
+    /// Find the ceiling of the square root, i.e. the smallest `r` such that `r * r >= self`.
+    ///
+    /// The result always fits in `Self`, even for `Self::max_value()`.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    fn integer_sqrt_ceil(&self) -> Self
+    where
+        Self: Sized,
+    {
+        self.integer_sqrt_ceil_checked()
+            .expect("cannot calculate square root of negative number")
+    }
+
+    /// Find the ceiling of the square root, returning `None` if the number is negative (this can
+    /// never happen for unsigned types).
+    fn integer_sqrt_ceil_checked(&self) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Find the square root rounded to the nearest integer.
+    ///
+    /// There is never a tie to break: the square root of an integer is either an integer or
+    /// irrational, so it can never lie exactly halfway between two integers.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    fn integer_sqrt_round(&self) -> Self
+    where
+        Self: Sized,
+    {
+        self.integer_sqrt_round_checked()
+            .expect("cannot calculate square root of negative number")
+    }
+
+    /// Find the square root rounded to the nearest integer, returning `None` if the number is
+    /// negative (this can never happen for unsigned types).
+    fn integer_sqrt_round_checked(&self) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Find the integer square root and the remainder, i.e. `(root, rem)` with
+    /// `self == root * root + rem`, in a single pass of the algorithm.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    fn integer_sqrt_rem(&self) -> (Self, Self)
+    where
+        Self: Sized,
+    {
+        self.integer_sqrt_rem_checked()
+            .expect("cannot calculate square root of negative number")
+    }
+
+    /// Find the integer square root and the remainder, returning `None` if the number is negative
+    /// (this can never happen for unsigned types).
+    fn integer_sqrt_rem_checked(&self) -> Option<(Self, Self)>
+    where
+        Self: Sized;
+
+    /// Find the integer fourth root, i.e. the largest `r` such that `r * r * r * r <= self`.
+    ///
+    /// This takes the integer square root twice, which gives exactly the right answer: if
+    /// `m = isqrt(n)` and `r = isqrt(m)`, then `r^2 <= m <= sqrt(n)`, so `r^4 <= n`, and
+    /// `(r + 1)^2`, an integer greater than `m`, is at least `m + 1 > sqrt(n)`, so
+    /// `(r + 1)^4 > n`.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    fn integer_fourth_root(&self) -> Self
+    where
+        Self: Sized,
+    {
+        self.integer_sqrt().integer_sqrt()
+    }
+
+    /// Find the integer fourth root, returning `None` if the number is negative (this can never
+    /// happen for unsigned types).
+    fn integer_fourth_root_checked(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        self.integer_sqrt_checked().map(|root| root.integer_sqrt())
+    }
+
+    /// Find the integer square root of the absolute value of the number.
+    ///
+    /// Unlike `self.abs().integer_sqrt()` this works for `Self::min_value()` of the `i` family,
+    /// whose absolute value does not fit in `Self`. For unsigned types it is the same as
+    /// [`integer_sqrt`].
+    ///
+    /// [`integer_sqrt`]: #method.integer_sqrt
+    fn abs_integer_sqrt(&self) -> Self
+    where
+        Self: Sized;
+
+    /// The largest integer square root of any value of this type, i.e. the largest `r` such that
+    /// `r * r <= Self::max_value()`.
+    ///
+    /// Any `r <= Self::max_sqrt()` can be squared without overflowing.
+    fn max_sqrt() -> Self
+    where
+        Self: Sized;
+
+    /// Find the floor and ceiling of the square root, and whether it is exact, from a single pass
+    /// of the algorithm.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    fn sqrt_full(&self) -> SqrtResult<Self>
+    where
+        Self: Sized,
+    {
+        self.sqrt_full_checked()
+            .expect("cannot calculate square root of negative number")
+    }
+
+    /// Find the floor and ceiling of the square root, and whether it is exact, returning `None`
+    /// if the number is negative (this can never happen for unsigned types).
+    fn sqrt_full_checked(&self) -> Option<SqrtResult<Self>>
+    where
+        Self: Sized;
+
+    /// Find the square root of a perfect square, returning `None` if the number is not a perfect
+    /// square (including when it is negative).
+    fn exact_sqrt(&self) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Find the integer square root, rounded as selected by `rounding`.
+    ///
+    /// This is equivalent to calling [`integer_sqrt`], [`integer_sqrt_ceil`] or
+    /// [`integer_sqrt_round`], for when the choice is only known at runtime.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    ///
+    /// [`integer_sqrt`]: #method.integer_sqrt
+    /// [`integer_sqrt_ceil`]: #method.integer_sqrt_ceil
+    /// [`integer_sqrt_round`]: #method.integer_sqrt_round
+    fn integer_sqrt_with(&self, rounding: Rounding) -> Self
+    where
+        Self: Sized,
+    {
+        self.integer_sqrt_with_checked(rounding)
+            .expect("cannot calculate square root of negative number")
+    }
+
+    /// Find the integer square root, rounded as selected by `rounding`, returning `None` if the
+    /// number is negative (this can never happen for unsigned types).
+    fn integer_sqrt_with_checked(&self, rounding: Rounding) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        match rounding {
+            Rounding::Floor => self.integer_sqrt_checked(),
+            Rounding::Ceil => self.integer_sqrt_ceil_checked(),
+            Rounding::Nearest => self.integer_sqrt_round_checked(),
+        }
+    }
 }
 
-impl<T: num_traits::PrimInt> IntegerSquareRoot for T {
+impl<T: Primitive> IntegerSquareRoot for T {
     fn integer_sqrt_checked(&self) -> Option<Self> {
-        use core::cmp::Ordering;
-        match self.cmp(&T::zero()) {
-            // Hopefully this will be stripped for unsigned numbers (impossible condition)
-            Ordering::Less => return None,
-            Ordering::Equal => return Some(T::zero()),
-            _ => {}
-        }
-
-        // Compute bit, the largest power of 4 <= n
-        let max_shift: u32 = T::zero().leading_zeros() - 1;
-        let shift: u32 = (max_shift - self.leading_zeros()) & !1;
-        let mut bit = T::one().unsigned_shl(shift);
-
-        // Algorithm based on the implementation in:
-        // https://en.wikipedia.org/wiki/Methods_of_computing_square_roots#Binary_numeral_system_(base_2)
-        // Note that result/bit are logically unsigned (even if T is signed).
-        let mut n = *self;
-        let mut result = T::zero();
-        while bit != T::zero() {
-            if n >= (result + bit) {
-                n = n - (result + bit);
-                result = result.unsigned_shr(1) + bit;
-            } else {
-                result = result.unsigned_shr(1);
+        self.integer_sqrt_rem_checked().map(|(root, _)| root)
+    }
+
+    fn integer_sqrt_or_zero(&self) -> Self {
+        self.integer_sqrt_checked().unwrap_or_else(T::zero)
+    }
+
+    fn integer_sqrt_ceil_checked(&self) -> Option<Self> {
+        // The floor root is at most half as wide as `self`, so `root + 1` cannot overflow.
+        self.integer_sqrt_rem_checked()
+            .map(|(root, rem)| root + from_bool(rem != T::zero()))
+    }
+
+    fn integer_sqrt_round_checked(&self) -> Option<Self> {
+        // sqrt(n) >= root + 1/2 exactly when n >= root^2 + root + 1/4, i.e. (for integers) when
+        // n - root^2 > root. Comparing the remainder keeps this free of overflow.
+        self.integer_sqrt_rem_checked()
+            .map(|(root, rem)| root + from_bool(rem > root))
+    }
+
+    fn abs_integer_sqrt(&self) -> Self {
+        if *self >= T::zero() {
+            return self.integer_sqrt();
+        }
+        // |n| does not fit for T::min_value(), but |n| - 1 = -(n + 1) always does. Writing
+        // |n| - 1 = root^2 + rem, |n| is the next square (root + 1)^2 exactly when rem == 2 * root.
+        let (root, rem) = (T::zero() - (*self + T::one())).integer_sqrt_rem();
+        if rem == root + root {
+            root + T::one()
+        } else {
+            root
+        }
+    }
+
+    fn sqrt_bits_checked(&self) -> Option<u32> {
+        if *self < T::zero() {
+            return None;
+        }
+        let bits = T::zero().leading_zeros() - self.leading_zeros();
+        Some(bits.div_ceil(2))
+    }
+
+    fn sqrt_estimate_checked(&self) -> Option<(Self, Self)> {
+        let bits = self.sqrt_bits_checked()?;
+        if bits == 0 {
+            return Some((T::zero(), T::zero()));
+        }
+        // The root has exactly `bits` bits, so lies in [2^(bits-1), 2^bits - 1]. `bits` is at
+        // most half the width of `T`, so neither bound can overflow.
+        let lower = T::one().unsigned_shl(bits - 1);
+        Some((lower, lower + (lower - T::one())))
+    }
+
+    fn integer_sqrt_with_hint_checked(&self, hint: Self) -> Option<Self> {
+        // How far the hint is walked before giving up on it
+        const MAX_STEPS: u32 = 4;
+
+        if *self < T::zero() {
+            return None;
+        }
+        let fits = |root: T| match root.checked_mul(&root) {
+            Some(square) => square <= *self,
+            None => false,
+        };
+        let mut root = if hint < T::zero() { T::zero() } else { hint };
+        let mut steps = 0;
+        // 0 always fits, so this stops before going below it
+        while !fits(root) {
+            if steps == MAX_STEPS {
+                return self.integer_sqrt_checked();
+            }
+            root = root - T::one();
+            steps += 1;
+        }
+        // root fits, so it is at most the largest root of `T` and `root + 1` cannot overflow
+        while fits(root + T::one()) {
+            if steps == MAX_STEPS {
+                return self.integer_sqrt_checked();
+            }
+            root = root + T::one();
+            steps += 1;
+        }
+        Some(root)
+    }
+
+    fn max_sqrt() -> Self {
+        T::max_value().integer_sqrt()
+    }
+
+    fn sqrt_full_checked(&self) -> Option<SqrtResult<Self>> {
+        self.integer_sqrt_rem_checked().map(|(root, rem)| {
+            let is_exact = rem == T::zero();
+            SqrtResult {
+                floor: root,
+                ceil: root + from_bool(!is_exact),
+                is_exact,
+            }
+        })
+    }
+
+    fn exact_sqrt(&self) -> Option<Self> {
+        match self.integer_sqrt_rem_checked() {
+            Some((root, rem)) if rem == T::zero() => Some(root),
+            _ => None,
+        }
+    }
+
+    fn integer_sqrt_rem_checked(&self) -> Option<(Self, Self)> {
+        // Hopefully this will be stripped for unsigned numbers (impossible condition)
+        if *self < T::zero() {
+            return None;
+        }
+        Some(sqrt_rem_non_negative(*self))
+    }
+}
+
+/// Find the integer square root of non-negative `n` and its remainder, when the build takes
+/// roots a bit at a time. The remainder is left over from the method itself, so neither the
+/// roots nor anything found from their remainders multiply, for cores without a multiplier such
+/// as the Cortex-M0 and RV32E.
+#[cfg(any(
+    feature = "wcet",
+    feature = "algo-bitwise",
+    all(
+        target_pointer_width = "16",
+        not(feature = "algo-binary-search"),
+        not(feature = "algo-cordic"),
+        not(feature = "algo-halley"),
+        not(feature = "algo-newton"),
+        not(all(feature = "core-isqrt", has_core_isqrt))
+    )
+))]
+#[inline]
+fn sqrt_rem_non_negative<T: Primitive>(n: T) -> (T, T) {
+    if cfg!(feature = "wcet") {
+        bitwise::bitwise_sqrt_fixed_rem(n)
+    } else {
+        bitwise::bitwise_sqrt_rem(n)
+    }
+}
+
+/// Find the integer square root of non-negative `n` and its remainder, by squaring the root.
+#[cfg(not(any(
+    feature = "wcet",
+    feature = "algo-bitwise",
+    all(
+        target_pointer_width = "16",
+        not(feature = "algo-binary-search"),
+        not(feature = "algo-cordic"),
+        not(feature = "algo-halley"),
+        not(feature = "algo-newton"),
+        not(all(feature = "core-isqrt", has_core_isqrt))
+    )
+)))]
+#[inline]
+fn sqrt_rem_non_negative<T: Primitive>(n: T) -> (T, T) {
+    let root = sqrt_non_negative(n);
+    (root, n - root * root)
+}
+
+/// Find the integer square root of non-negative `n` in a fixed number of rounds for its width,
+/// as chosen for the whole build by the `wcet` feature, which takes precedence over the others.
+#[cfg(feature = "wcet")]
+#[inline]
+fn sqrt_non_negative<T: Primitive>(n: T) -> T {
+    bitwise::bitwise_sqrt_fixed(n)
+}
+
+/// Find the integer square root of non-negative `n` a bit at a time, as chosen for the whole
+/// build by the `algo-bitwise` feature.
+///
+/// This is also the default on 16 bit targets, such as AVR and MSP430. They have no divider, so
+/// Newton's method pulls in slow software division, and AVR copies tables from flash into its
+/// small RAM.
+#[cfg(all(
+    not(feature = "wcet"),
+    any(
+        feature = "algo-bitwise",
+        all(
+            target_pointer_width = "16",
+            not(feature = "algo-binary-search"),
+            not(feature = "algo-cordic"),
+            not(feature = "algo-halley"),
+            not(feature = "algo-newton"),
+            not(all(feature = "core-isqrt", has_core_isqrt))
+        )
+    )
+))]
+#[inline]
+fn sqrt_non_negative<T: Primitive>(n: T) -> T {
+    bitwise::bitwise_sqrt(n)
+}
+
+/// Find the integer square root of non-negative `n` by CORDIC, as chosen for the whole build by
+/// the `algo-cordic` feature.
+#[cfg(all(
+    feature = "algo-cordic",
+    not(any(feature = "wcet", feature = "algo-bitwise"))
+))]
+#[inline]
+fn sqrt_non_negative<T: Primitive>(n: T) -> T {
+    cordic::cordic_sqrt(n)
+}
+
+/// Find the integer square root of non-negative `n` by binary search, as chosen for the whole
+/// build by the `algo-binary-search` feature.
+#[cfg(all(
+    feature = "algo-binary-search",
+    not(any(feature = "wcet", feature = "algo-bitwise", feature = "algo-cordic"))
+))]
+#[inline]
+fn sqrt_non_negative<T: Primitive>(n: T) -> T {
+    binary_search::binary_search_sqrt(n)
+}
+
+/// Find the integer square root of non-negative `n` by Halley's method, as chosen for the whole
+/// build by the `algo-halley` feature.
+#[cfg(all(
+    feature = "algo-halley",
+    not(any(
+        feature = "wcet",
+        feature = "algo-bitwise",
+        feature = "algo-cordic",
+        feature = "algo-binary-search"
+    ))
+))]
+#[inline]
+fn sqrt_non_negative<T: Primitive>(n: T) -> T {
+    halley::halley_sqrt(n)
+}
+
+/// Find the integer square root of non-negative `n` by Newton's method, as chosen for the whole
+/// build by the `algo-newton` feature.
+#[cfg(all(
+    feature = "algo-newton",
+    not(any(
+        feature = "wcet",
+        feature = "algo-bitwise",
+        feature = "algo-cordic",
+        feature = "algo-binary-search",
+        feature = "algo-halley"
+    ))
+))]
+#[inline]
+fn sqrt_non_negative<T: Primitive>(n: T) -> T {
+    newton::newton_sqrt(n)
+}
+
+/// Find the integer square root of non-negative `n` with the standard library's `isqrt`, as
+/// chosen for the whole build by the `core-isqrt` feature, on compilers that have it.
+///
+/// Non-negative numbers fit in the unsigned type of the same width, whose root is the same.
+#[cfg(all(
+    feature = "core-isqrt",
+    has_core_isqrt,
+    not(any(
+        feature = "wcet",
+        feature = "algo-bitwise",
+        feature = "algo-cordic",
+        feature = "algo-binary-search",
+        feature = "algo-halley",
+        feature = "algo-newton"
+    ))
+))]
+#[allow(clippy::incompatible_msrv)]
+#[inline]
+fn sqrt_non_negative<T: Primitive>(n: T) -> T {
+    // The width is a constant, so only one branch is kept
+    match T::zero().count_zeros() {
+        8 => T::from(n.to_u8().unwrap().isqrt()).unwrap(),
+        16 => T::from(n.to_u16().unwrap().isqrt()).unwrap(),
+        32 => T::from(n.to_u32().unwrap().isqrt()).unwrap(),
+        64 => T::from(n.to_u64().unwrap().isqrt()).unwrap(),
+        _ => T::from(n.to_u128().unwrap().isqrt()).unwrap(),
+    }
+}
+
+/// Find the integer square root of non-negative `n`, with the fastest method for its width.
+///
+/// The widths are constants, so the choice is made at compile time, and each width only keeps
+/// its own branch. `usize` and `isize` take the branch of the fixed width type that they match
+/// on the target.
+#[cfg(not(any(
+    feature = "wcet",
+    feature = "algo-bitwise",
+    feature = "algo-cordic",
+    feature = "algo-binary-search",
+    feature = "algo-halley",
+    feature = "algo-newton",
+    all(feature = "core-isqrt", has_core_isqrt),
+    target_pointer_width = "16"
+)))]
+#[inline]
+fn sqrt_non_negative<T: Primitive>(n: T) -> T {
+    let bits = T::zero().count_zeros();
+    // The roots of the 8 bit types are looked up
+    if bits <= 8 {
+        return table::table_sqrt(n);
+    }
+    // With `std`, `libm` or a hardware square root, the floating point square root is faster
+    // still for types it can represent closely enough to correct exactly
+    #[cfg(any(
+        feature = "std",
+        feature = "libm",
+        all(feature = "sse2", target_feature = "sse2"),
+        all(feature = "vsqrt-f64", target_arch = "arm", target_abi = "eabihf")
+    ))]
+    {
+        if bits <= 64 {
+            return float::float_sqrt(n);
+        }
+    }
+    // Some FPUs, like the Cortex-M4F's, only have a single precision square root
+    #[cfg(all(
+        all(feature = "vsqrt", target_arch = "arm", target_abi = "eabihf"),
+        not(any(feature = "std", feature = "libm", feature = "vsqrt-f64"))
+    ))]
+    {
+        if bits <= 32 {
+            return T::from(float::float_sqrt_u32(n.to_u32().unwrap())).unwrap();
+        }
+    }
+    // The 128 bit roots are found from the 64 bit root of their top half
+    if bits == 128 {
+        return T::from(karatsuba::karatsuba_sqrt_u128(n.to_u128().unwrap())).unwrap();
+    }
+    // Likewise the 64 bit roots on 32 bit targets, where 64 bit division is slow
+    #[cfg(target_pointer_width = "32")]
+    {
+        if bits == 64 {
+            return T::from(karatsuba::karatsuba_sqrt_u64(n.to_u64().unwrap())).unwrap();
+        }
+    }
+    // Newton's method takes fewer, but more expensive, steps than computing a bit at a time. For
+    // the 16 and 32 bit types, seeding it from a table of roots cuts it down to a couple of steps
+    if bits >= 64 {
+        newton::newton_sqrt(n)
+    } else {
+        table::table_seeded_sqrt(n)
+    }
+}
+
+/// A trait implementing integer square root in place.
+///
+/// For primitive types this is no different from assigning the result of
+/// [`IntegerSquareRoot::integer_sqrt`], but types that own their storage can implement it to reuse
+/// that storage rather than allocating a new value.
+///
+/// It is also implemented for slices of primitive types, which root every element. This is the
+/// batch interface: slices of `u32` and `u64` are rooted several elements at a time with NEON on
+/// aarch64, with SIMD128 on wasm32 when the target enables it, and with AVX2 or AVX-512 on x86-64
+/// with the `std` feature, which detects them at runtime.
+///
+/// Unlike [`IntegerSquareRoot`], whose methods all return `Self` and so cannot be called on a
+/// trait object, this trait is usable as `dyn IntegerSqrtAssign`, which allows numbers of
+/// different types to be stored and rooted together:
+///
+/// ```
+/// # extern crate integer_sqrt;
+/// use integer_sqrt::IntegerSqrtAssign;
+///
+/// # fn main() {
+/// let (mut a, mut b) = (17u8, -4i64);
+/// {
+///     let mut values: [&mut dyn IntegerSqrtAssign; 2] = [&mut a, &mut b];
+///     for value in values.iter_mut() {
+///         value.integer_sqrt_assign_checked();
+///     }
+/// }
+/// assert_eq!((a, b), (4, -4));
+/// # }
+/// ```
+///
+/// [`IntegerSquareRoot::integer_sqrt`]: ./trait.IntegerSquareRoot.html#method.integer_sqrt
+/// [`IntegerSquareRoot`]: ./trait.IntegerSquareRoot.html
+pub trait IntegerSqrtAssign {
+    /// Replace the number with its integer square root.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    fn integer_sqrt_assign(&mut self);
+
+    /// Replace the number with its integer square root, returning `false` and leaving the number
+    /// unchanged if it is negative (this can never happen for unsigned types).
+    fn integer_sqrt_assign_checked(&mut self) -> bool;
+}
+
+impl<T: Primitive> IntegerSqrtAssign for T {
+    fn integer_sqrt_assign(&mut self) {
+        *self = self.integer_sqrt();
+    }
+
+    fn integer_sqrt_assign_checked(&mut self) -> bool {
+        match self.integer_sqrt_checked() {
+            Some(root) => {
+                *self = root;
+                true
             }
-            bit = bit.unsigned_shr(2);
+            None => false,
         }
-        Some(result)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::IntegerSquareRoot;
-    use core::{i8, u16, u64, u8};
-
     macro_rules! gen_tests {
         ($($type:ty => $fn_name:ident),*) => {
             $(
@@ -89,7 +970,7 @@ mod tests {
                 fn $fn_name() {
                     let newton_raphson = |val, square| 0.5 * (val + (square / val as $type) as f64);
                     let max_sqrt = {
-                        let square = <$type>::max_value();
+                        let square = <$type>::MAX;
                         let mut value = (square as f64).sqrt();
                         for _ in 0..2 {
                             value = newton_raphson(value, square);
@@ -110,8 +991,8 @@ mod tests {
                         (4, 2),
                         (81, 9),
                         (80, 8),
-                        (<$type>::max_value(), max_sqrt),
-                        (<$type>::max_value() - 1, max_sqrt),
+                        (<$type>::MAX, max_sqrt),
+                        (<$type>::MAX - 1, max_sqrt),
                     ];
                     for &(in_, out) in tests.iter() {
                         assert_eq!(in_.integer_sqrt(), out, "in {}", in_);
@@ -145,10 +1026,505 @@ mod tests {
             (4, 2),
             (81, 9),
             (80, 8),
-            (i128::max_value(), 13_043_817_825_332_782_212),
+            (i128::MAX, 13_043_817_825_332_782_212),
         ];
         for &(in_, out) in tests.iter() {
             assert_eq!(in_.integer_sqrt(), out, "in {}", in_);
         }
     }
+
+    macro_rules! gen_ceil_tests {
+        ($($type:ty => $fn_name:ident),*) => {
+            $(
+                #[test]
+                fn $fn_name() {
+                    let tests: [($type, $type); 8] = [
+                        (0, 0),
+                        (1, 1),
+                        (2, 2),
+                        (3, 2),
+                        (4, 2),
+                        (5, 3),
+                        (81, 9),
+                        (82, 10),
+                    ];
+                    for &(in_, out) in tests.iter() {
+                        assert_eq!(in_.integer_sqrt_ceil(), out, "in {}", in_);
+                    }
+                    for &in_ in [<$type>::MAX, <$type>::MAX - 1, <$type>::MAX / 3].iter() {
+                        let ceil = in_.integer_sqrt_ceil();
+                        let floor = in_.integer_sqrt();
+                        // `in_` is never a perfect square here
+                        assert_eq!(ceil, floor + 1, "in {}", in_);
+                    }
+                }
+            )*
+        };
+    }
+
+    gen_ceil_tests! {
+        i8 => i8_ceil_test,
+        u8 => u8_ceil_test,
+        i16 => i16_ceil_test,
+        u16 => u16_ceil_test,
+        i32 => i32_ceil_test,
+        u32 => u32_ceil_test,
+        i64 => i64_ceil_test,
+        u64 => u64_ceil_test,
+        i128 => i128_ceil_test,
+        u128 => u128_ceil_test,
+        isize => isize_ceil_test,
+        usize => usize_ceil_test
+    }
+
+    #[test]
+    fn round_test() {
+        let tests: [(u32, u32); 9] = [
+            (0, 0),
+            (1, 1),
+            (2, 1),
+            (3, 2),
+            (6, 2),
+            (7, 3),
+            (12, 3),
+            (13, 4),
+            (u32::MAX, 65_536),
+        ];
+        for &(in_, out) in tests.iter() {
+            assert_eq!(in_.integer_sqrt_round(), out, "in {}", in_);
+        }
+        assert_eq!(u8::MAX.integer_sqrt_round(), 16);
+        assert_eq!(i8::MAX.integer_sqrt_round(), 11);
+        assert_eq!(u128::MAX.integer_sqrt_round(), 1 << 64);
+        assert_eq!(i128::MAX.integer_sqrt_round(), 13_043_817_825_332_782_212);
+        assert_eq!((-1i32).integer_sqrt_round_checked(), None);
+    }
+
+    #[test]
+    fn rem_test() {
+        let tests: [(i32, (i32, i32)); 6] = [
+            (0, (0, 0)),
+            (1, (1, 0)),
+            (2, (1, 1)),
+            (8, (2, 4)),
+            (9, (3, 0)),
+            (i32::MAX, (46_340, 88_047)),
+        ];
+        for &(in_, out) in tests.iter() {
+            assert_eq!(in_.integer_sqrt_rem(), out, "in {}", in_);
+        }
+        assert_eq!(
+            u128::MAX.integer_sqrt_rem(),
+            (u64::MAX as u128, 2 * u64::MAX as u128)
+        );
+        assert_eq!((-1i32).integer_sqrt_rem_checked(), None);
+        for n in 0..=u16::MAX {
+            let (root, rem) = n.integer_sqrt_rem();
+            assert_eq!(root, n.integer_sqrt());
+            assert_eq!(
+                u32::from(root) * u32::from(root) + u32::from(rem),
+                u32::from(n)
+            );
+        }
+    }
+
+    /// The methods all start from a shift found from `leading_zeros`, rounded to an even shift,
+    /// so check the numbers on either side of every power of 4.
+    #[test]
+    fn power_of_four_test() {
+        for shift in 0..64 {
+            let root = 1u128 << shift;
+            let square = root * root;
+            assert_eq!(square.integer_sqrt_rem(), (root, 0), "in {}", square);
+            assert_eq!(
+                (square - 1).integer_sqrt_rem(),
+                (root - 1, 2 * (root - 1)),
+                "in {}",
+                square - 1
+            );
+            assert_eq!(
+                (square + 1).integer_sqrt_rem(),
+                (root, 1),
+                "in {}",
+                square + 1
+            );
+            assert_eq!(
+                (2 * square).integer_sqrt(),
+                (2 * square).integer_sqrt_ceil() - 1
+            );
+            if shift < 63 {
+                let square = square as i128;
+                assert_eq!(square.integer_sqrt(), root as i128, "in {}", square);
+                assert_eq!(
+                    (square - 1).integer_sqrt(),
+                    root as i128 - 1,
+                    "in {}",
+                    square
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn exact_test() {
+        let tests: [(i64, Option<i64>); 8] = [
+            (0, Some(0)),
+            (1, Some(1)),
+            (2, None),
+            (4, Some(2)),
+            (99, None),
+            (100, Some(10)),
+            (-4, None),
+            (i64::MAX, None),
+        ];
+        for &(in_, out) in tests.iter() {
+            assert_eq!(in_.exact_sqrt(), out, "in {}", in_);
+        }
+        assert_eq!(
+            ((u64::MAX as u128) * (u64::MAX as u128)).exact_sqrt(),
+            Some(u64::MAX as u128)
+        );
+        assert_eq!(0xFFFFu32.pow(2).exact_sqrt(), Some(0xFFFF));
+        assert_eq!((0xFFFFu32.pow(2) - 1).exact_sqrt(), None);
+    }
+
+    #[test]
+    fn fourth_root_test() {
+        for n in 0..=u16::MAX {
+            let r = u64::from(n.integer_fourth_root());
+            let n = u64::from(n);
+            assert!(r.pow(4) <= n && (r + 1).pow(4) > n, "in {}", n);
+        }
+        assert_eq!(u64::MAX.integer_fourth_root(), u16::MAX as u64);
+        assert_eq!(u128::MAX.integer_fourth_root(), u32::MAX as u128);
+        assert_eq!((1u128 << 124).integer_fourth_root(), 1 << 31);
+        assert_eq!(((1u128 << 124) - 1).integer_fourth_root(), (1 << 31) - 1);
+        assert_eq!(i32::MAX.integer_fourth_root(), 215);
+        assert_eq!((-16i32).integer_fourth_root_checked(), None);
+    }
+
+    #[test]
+    fn abs_test() {
+        for n in i16::MIN..=i16::MAX {
+            let expected = i32::from(n).unsigned_abs().integer_sqrt() as i16;
+            assert_eq!(n.abs_integer_sqrt(), expected, "in {}", n);
+        }
+        assert_eq!(i8::MIN.abs_integer_sqrt(), 11);
+        assert_eq!(i64::MIN.abs_integer_sqrt(), 3_037_000_499);
+        assert_eq!(i128::MIN.abs_integer_sqrt(), 13_043_817_825_332_782_212);
+        assert_eq!((-(1i128 << 126)).abs_integer_sqrt(), 1 << 63);
+        assert_eq!(u32::MAX.abs_integer_sqrt(), u32::MAX.integer_sqrt());
+    }
+
+    #[test]
+    fn max_sqrt_test() {
+        macro_rules! check {
+            ($($type:ty => $max_sqrt:expr),*) => {
+                $(
+                    let max_sqrt: $type = $max_sqrt;
+                    assert_eq!(<$type>::max_sqrt(), max_sqrt);
+                    assert!(max_sqrt.checked_mul(max_sqrt).is_some());
+                    assert!((max_sqrt + 1).checked_mul(max_sqrt + 1).is_none());
+                )*
+            };
+        }
+        check! {
+            u8 => 15,
+            i8 => 11,
+            u16 => 255,
+            i16 => 181,
+            u32 => 65_535,
+            i32 => 46_340,
+            u64 => 4_294_967_295,
+            i64 => 3_037_000_499,
+            u128 => 18_446_744_073_709_551_615,
+            i128 => 13_043_817_825_332_782_212,
+            usize => usize::MAX.integer_sqrt(),
+            isize => isize::MAX.integer_sqrt()
+        }
+    }
+
+    #[test]
+    fn pointer_width() {
+        // `usize` takes the same path as the fixed width type of its size
+        for shift in 0..usize::BITS {
+            for n in [1usize << shift, (1 << shift) - 1, usize::MAX >> shift] {
+                #[cfg(target_pointer_width = "64")]
+                assert_eq!(
+                    n.integer_sqrt() as u64,
+                    (n as u64).integer_sqrt(),
+                    "in {}",
+                    n
+                );
+                #[cfg(target_pointer_width = "32")]
+                assert_eq!(
+                    n.integer_sqrt() as u32,
+                    (n as u32).integer_sqrt(),
+                    "in {}",
+                    n
+                );
+                #[cfg(target_pointer_width = "16")]
+                assert_eq!(
+                    n.integer_sqrt() as u16,
+                    (n as u16).integer_sqrt(),
+                    "in {}",
+                    n
+                );
+                let n = (n >> 1) as isize;
+                assert_eq!(
+                    n.integer_sqrt() as usize,
+                    (n as usize).integer_sqrt(),
+                    "in {}",
+                    n
+                );
+            }
+        }
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(
+            (usize::MAX.integer_sqrt(), isize::MAX.integer_sqrt()),
+            (4_294_967_295, 3_037_000_499)
+        );
+        #[cfg(target_pointer_width = "32")]
+        assert_eq!(
+            (usize::MAX.integer_sqrt(), isize::MAX.integer_sqrt()),
+            (65_535, 46_340)
+        );
+        #[cfg(target_pointer_width = "16")]
+        assert_eq!(
+            (usize::MAX.integer_sqrt(), isize::MAX.integer_sqrt()),
+            (255, 181)
+        );
+    }
+
+    #[test]
+    fn full_test() {
+        use super::SqrtResult;
+        for &n in [0i64, 1, 2, 3, 4, 5, 24, 25, 26, i64::MAX].iter() {
+            let full = n.sqrt_full();
+            assert_eq!(
+                full,
+                SqrtResult {
+                    floor: n.integer_sqrt(),
+                    ceil: n.integer_sqrt_ceil(),
+                    is_exact: n.exact_sqrt().is_some(),
+                },
+                "in {}",
+                n
+            );
+        }
+        assert_eq!((-1i64).sqrt_full_checked(), None);
+    }
+
+    #[test]
+    fn sqrt_bits_test() {
+        let bit_length = |n: u64| 64 - n.leading_zeros();
+        for n in 0..=u16::MAX {
+            assert_eq!(
+                n.sqrt_bits(),
+                bit_length(n.integer_sqrt().into()),
+                "in {}",
+                n
+            );
+        }
+        for shift in 0..64 {
+            for &n in [1u64 << shift, (1 << shift) - 1, (1 << shift) + 1].iter() {
+                assert_eq!(n.sqrt_bits(), bit_length(n.integer_sqrt()), "in {}", n);
+            }
+        }
+        assert_eq!(u64::MAX.sqrt_bits(), 32);
+        assert_eq!(i128::MAX.sqrt_bits(), 64);
+        assert_eq!((-1i8).sqrt_bits_checked(), None);
+    }
+
+    #[test]
+    fn or_zero_test() {
+        for n in i16::MIN..=i16::MAX {
+            let expected = if n < 0 { 0 } else { n.integer_sqrt() };
+            assert_eq!(n.integer_sqrt_or_zero(), expected, "in {}", n);
+        }
+        assert_eq!(u64::MAX.integer_sqrt_or_zero(), u64::MAX.integer_sqrt());
+        assert_eq!(i128::MIN.integer_sqrt_or_zero(), 0);
+    }
+
+    #[test]
+    fn sqrt_estimate_test() {
+        for n in 0..=u16::MAX {
+            let (lower, upper) = n.sqrt_estimate();
+            let root = n.integer_sqrt();
+            assert!(lower <= root && root <= upper, "in {}", n);
+            assert!(upper <= lower * 2, "in {}", n);
+        }
+        for shift in 0..128 {
+            for &n in [1u128 << shift, (1 << shift) - 1, (1 << shift) + 1].iter() {
+                let (lower, upper) = n.sqrt_estimate();
+                let root = n.integer_sqrt();
+                assert!(lower <= root && root <= upper, "in {}", n);
+            }
+        }
+        assert_eq!(u8::MAX.sqrt_estimate(), (8, 15));
+        assert_eq!(i8::MAX.sqrt_estimate(), (8, 15));
+        assert_eq!(u128::MAX.sqrt_estimate(), (1 << 63, u64::MAX as u128));
+        assert_eq!((-1i32).sqrt_estimate_checked(), None);
+    }
+
+    #[test]
+    fn with_hint_test() {
+        let mut root = 0;
+        for n in 0..=u16::MAX {
+            root = n.integer_sqrt_with_hint(root);
+            assert_eq!(root, n.integer_sqrt(), "in {}", n);
+        }
+        for &hint in [i64::MIN, -1, 0, 1, 3_037_000_498, 3_037_000_500, i64::MAX].iter() {
+            for &n in [0i64, 1, 2, 1 << 40, i64::MAX - 1, i64::MAX].iter() {
+                assert_eq!(
+                    n.integer_sqrt_with_hint(hint),
+                    n.integer_sqrt(),
+                    "in {} hint {}",
+                    n,
+                    hint
+                );
+            }
+        }
+        assert_eq!(
+            u128::MAX.integer_sqrt_with_hint(u128::MAX),
+            u64::MAX as u128
+        );
+        assert_eq!((-1i32).integer_sqrt_with_hint_checked(0), None);
+    }
+
+    #[test]
+    fn unchecked_test() {
+        for n in 0..=u16::MAX {
+            assert_eq!(unsafe { n.integer_sqrt_unchecked() }, n.integer_sqrt());
+        }
+        assert_eq!(
+            unsafe { i128::MAX.integer_sqrt_unchecked() },
+            i128::max_sqrt()
+        );
+    }
+
+    #[test]
+    fn references_test() {
+        let values = [0i32, 3, 4, 99, -1];
+        let mut roots = values.iter().map(IntegerSquareRoot::integer_sqrt_checked);
+        assert_eq!(roots.next(), Some(Some(0)));
+        assert_eq!(roots.next(), Some(Some(1)));
+        assert_eq!(roots.next(), Some(Some(2)));
+        assert_eq!(roots.next(), Some(Some(9)));
+        assert_eq!(roots.next(), Some(None));
+        let n = &&&65u16;
+        assert_eq!(n.integer_sqrt(), 8);
+    }
+
+    #[test]
+    fn std_aliases_test() {
+        fn generic<T: IntegerSquareRoot>(n: T) -> (T, Option<T>) {
+            (n.isqrt(), n.checked_isqrt())
+        }
+        for n in 0..=u16::MAX {
+            assert_eq!(generic(n), (n.isqrt(), Some(n.isqrt())), "in {}", n);
+        }
+        for n in i16::MIN..=i16::MAX {
+            let checked = IntegerSquareRoot::checked_isqrt(&n);
+            assert_eq!(checked, n.checked_isqrt(), "in {}", n);
+        }
+        assert_eq!(IntegerSquareRoot::isqrt(&u128::MAX), u128::MAX.isqrt());
+    }
+
+    #[test]
+    fn by_value_test() {
+        fn generic<T: IntegerSquareRoot + Copy>(x: T) -> (T, Option<T>) {
+            (x.integer_sqrt(), x.integer_sqrt_checked())
+        }
+        assert_eq!(generic(65u16), (8, Some(8)));
+        let roots = [0i32, 3, 4, 99].iter().cloned().map(::isqrt);
+        assert!(roots.eq([0, 1, 2, 9].iter().cloned()));
+    }
+
+    #[test]
+    fn assign_test() {
+        use super::IntegerSqrtAssign;
+        let mut n = 99u64;
+        n.integer_sqrt_assign();
+        assert_eq!(n, 9);
+        let mut n = i128::MAX;
+        n.integer_sqrt_assign();
+        assert_eq!(n, i128::MAX.integer_sqrt());
+    }
+
+    #[test]
+    fn assign_checked_test() {
+        use super::IntegerSqrtAssign;
+        let (mut a, mut b, mut c) = (99u64, -1i32, 10i8);
+        let mut assigned = [false; 3];
+        {
+            let mut values: [&mut dyn IntegerSqrtAssign; 3] = [&mut a, &mut b, &mut c];
+            for (value, assigned) in values.iter_mut().zip(assigned.iter_mut()) {
+                *assigned = value.integer_sqrt_assign_checked();
+            }
+        }
+        assert_eq!(assigned, [true, false, true]);
+        assert_eq!((a, b, c), (9, -1, 3));
+    }
+
+    /// All of the traits can be made into trait objects, even though only the methods of
+    /// `IntegerSqrtAssign` can be called through one.
+    #[test]
+    fn object_safety() {
+        use super::{
+            FigurateRoots, IntegerCubeRoot, IntegerNthRoot, IntegerSqrtAssign, PerfectSquares,
+        };
+        let _: &dyn IntegerSquareRoot = &1u8;
+        let _: &dyn IntegerSqrtAssign = &1u8;
+        let _: &dyn IntegerCubeRoot = &1u8;
+        let _: &dyn IntegerNthRoot = &1u8;
+        let _: &dyn PerfectSquares = &1u8;
+        let _: &dyn FigurateRoots = &1u8;
+    }
+
+    #[test]
+    #[should_panic]
+    fn assign_negative() {
+        use super::IntegerSqrtAssign;
+        let mut n = -1i32;
+        n.integer_sqrt_assign();
+    }
+
+    #[test]
+    fn with_rounding_test() {
+        use super::Rounding;
+        for &n in [0u64, 1, 2, 3, 7, 8, 9, 10, 1 << 40, u64::MAX].iter() {
+            assert_eq!(n.integer_sqrt_with(Rounding::Floor), n.integer_sqrt());
+            assert_eq!(n.integer_sqrt_with(Rounding::Ceil), n.integer_sqrt_ceil());
+            assert_eq!(
+                n.integer_sqrt_with(Rounding::Nearest),
+                n.integer_sqrt_round()
+            );
+        }
+        assert_eq!((-4i8).integer_sqrt_with_checked(Rounding::Nearest), None);
+    }
+
+    #[test]
+    fn round_exhaustive_u16() {
+        for n in 0..=u16::MAX {
+            let r = u64::from(n.integer_sqrt_round());
+            // |r - sqrt(n)| < 1/2  <=>  (2r - 1)^2 < 4n < (2r + 1)^2
+            let n4 = 4 * u64::from(n);
+            assert!(
+                (r == 0 || (2 * r - 1) * (2 * r - 1) < n4) && n4 < (2 * r + 1) * (2 * r + 1),
+                "in {}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn ceil_exhaustive_u16() {
+        for n in 0..=u16::MAX {
+            let r = u32::from(n.integer_sqrt_ceil());
+            let n = u32::from(n);
+            assert!(r * r >= n && (r == 0 || (r - 1) * (r - 1) < n), "in {}", n);
+        }
+        assert_eq!((-1i32).integer_sqrt_ceil_checked(), None);
+    }
 }
@@ -0,0 +1,89 @@
+use {IntegerSquareRoot, NegativeSqrtError};
+
+/// A trait for taking the integer square root of the value inside an `Option` or `Result`.
+///
+/// This saves a nested closure in chains of checked arithmetic. The root of a negative number is
+/// reported in the same way as the failures already carried: as `None` for `Option`, and for
+/// `Result` as a [`NegativeSqrtError`] converted into the error type with `From`.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::{MapIntegerSquareRoot, NegativeSqrtError};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Error {
+///     Parse,
+///     Negative(i32),
+/// }
+///
+/// impl From<NegativeSqrtError<i32>> for Error {
+///     fn from(err: NegativeSqrtError<i32>) -> Self {
+///         Error::Negative(*err.value())
+///     }
+/// }
+///
+/// # fn main() {
+/// assert_eq!(50i32.checked_sub(1).map_integer_sqrt(), Some(7));
+/// assert_eq!(1i32.checked_sub(50).map_integer_sqrt(), None);
+///
+/// let parse = |s: &str| s.parse::<i32>().map_err(|_| Error::Parse);
+/// assert_eq!(parse("49").map_integer_sqrt(), Ok(7));
+/// assert_eq!(parse("-49").map_integer_sqrt(), Err(Error::Negative(-49)));
+/// assert_eq!(parse("x").map_integer_sqrt(), Err(Error::Parse));
+/// # }
+/// ```
+///
+/// [`NegativeSqrtError`]: ./struct.NegativeSqrtError.html
+pub trait MapIntegerSquareRoot {
+    /// The container type holding the root.
+    type Output;
+
+    /// Find the integer square root of the contained value, passing on `None` or `Err`
+    /// unchanged.
+    fn map_integer_sqrt(self) -> Self::Output;
+}
+
+impl<T: IntegerSquareRoot> MapIntegerSquareRoot for Option<T> {
+    type Output = Option<T>;
+
+    #[inline]
+    fn map_integer_sqrt(self) -> Option<T> {
+        self.and_then(|n| n.integer_sqrt_checked())
+    }
+}
+
+impl<T, E> MapIntegerSquareRoot for Result<T, E>
+where
+    T: IntegerSquareRoot + Clone,
+    E: From<NegativeSqrtError<T>>,
+{
+    type Output = Result<T, E>;
+
+    #[inline]
+    fn map_integer_sqrt(self) -> Result<T, E> {
+        self.and_then(|n| n.try_integer_sqrt().map_err(E::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use MapIntegerSquareRoot;
+    use NegativeSqrtError;
+
+    #[test]
+    fn option() {
+        assert_eq!(Some(17u8).map_integer_sqrt(), Some(4));
+        assert_eq!(Some(-17i8).map_integer_sqrt(), None);
+        assert_eq!(None::<u32>.map_integer_sqrt(), None);
+    }
+
+    #[test]
+    fn result() {
+        let ok: Result<i64, NegativeSqrtError<i64>> = Ok(17);
+        assert_eq!(ok.map_integer_sqrt(), Ok(4));
+        let negative: Result<i64, NegativeSqrtError<i64>> = Ok(-17);
+        assert_eq!(*negative.map_integer_sqrt().unwrap_err().value(), -17);
+        let err: Result<i64, NegativeSqrtError<i64>> = Err(NegativeSqrtError::new(-3));
+        assert_eq!(*err.map_integer_sqrt().unwrap_err().value(), -3);
+    }
+}
@@ -0,0 +1,160 @@
+use IntegerSquareRoot;
+
+/// A trait for taking the integer square root straight into a narrower type.
+///
+/// The root of an `N` bit number always fits in `N / 2` bits, so each primitive type names the
+/// smallest unsigned type holding every one of its roots as [`Root`], and the root can be
+/// converted into any type that type converts into without loss. Conversions that could fail
+/// are rejected at compile time instead of needing `try_into().unwrap()`.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::SqrtInto;
+///
+/// # fn main() {
+/// let root: u32 = u64::MAX.sqrt_into();
+/// assert_eq!(root, u32::MAX);
+/// let root: i64 = 1_000_000i64.sqrt_into();
+/// assert_eq!(root, 1000);
+/// # }
+/// ```
+///
+/// [`Root`]: #associatedtype.Root
+pub trait SqrtInto: IntegerSquareRoot {
+    /// The smallest unsigned type that holds the integer square root of every value of `Self`.
+    type Root;
+
+    /// Find the integer square root, converted into `U`.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    fn sqrt_into<U: From<Self::Root>>(&self) -> U
+    where
+        Self: Sized,
+    {
+        self.sqrt_into_checked()
+            .expect("cannot calculate square root of negative number")
+    }
+
+    /// Find the integer square root converted into `U`, returning `None` if the number is
+    /// negative (this can never happen for unsigned types).
+    fn sqrt_into_checked<U: From<Self::Root>>(&self) -> Option<U>
+    where
+        Self: Sized;
+}
+
+/// A trait for taking the integer square root as the half-width type, e.g. `u32` for `u64`.
+///
+/// The return type records that the root always fits, so APIs can take the narrow type and
+/// skip overflow checks that could never fail. It is implemented for every type implementing
+/// [`SqrtInto`], giving its [`Root`] type.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::NarrowingSqrt;
+///
+/// # fn main() {
+/// let root: u64 = u128::MAX.narrowing_sqrt();
+/// assert_eq!(root, u64::MAX);
+/// assert_eq!((-1i32).narrowing_sqrt_checked(), None);
+/// # }
+/// ```
+///
+/// [`SqrtInto`]: ./trait.SqrtInto.html
+/// [`Root`]: ./trait.SqrtInto.html#associatedtype.Root
+pub trait NarrowingSqrt: SqrtInto {
+    /// Find the integer square root as the narrowest unsigned type that holds it.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    #[inline]
+    fn narrowing_sqrt(&self) -> Self::Root
+    where
+        Self: Sized,
+    {
+        self.sqrt_into()
+    }
+
+    /// Find the integer square root as the narrowest unsigned type that holds it, returning
+    /// `None` if the number is negative (this can never happen for unsigned types).
+    #[inline]
+    fn narrowing_sqrt_checked(&self) -> Option<Self::Root>
+    where
+        Self: Sized,
+    {
+        self.sqrt_into_checked()
+    }
+}
+
+impl<T: SqrtInto> NarrowingSqrt for T {}
+
+macro_rules! impl_sqrt_into {
+    ($($type:ty => $root:ty),*) => {
+        $(
+            impl SqrtInto for $type {
+                type Root = $root;
+
+                #[inline]
+                fn sqrt_into_checked<U: From<$root>>(&self) -> Option<U> {
+                    self.integer_sqrt_checked().map(|root| U::from(root as $root))
+                }
+            }
+        )*
+    };
+}
+
+impl_sqrt_into! {
+    u8 => u8,
+    i8 => u8,
+    u16 => u8,
+    i16 => u8,
+    u32 => u16,
+    i32 => u16,
+    u64 => u32,
+    i64 => u32,
+    u128 => u64,
+    i128 => u64
+}
+
+#[cfg(target_pointer_width = "16")]
+impl_sqrt_into!(usize => u8, isize => u8);
+#[cfg(target_pointer_width = "32")]
+impl_sqrt_into!(usize => u16, isize => u16);
+#[cfg(target_pointer_width = "64")]
+impl_sqrt_into!(usize => u32, isize => u32);
+
+#[cfg(test)]
+mod tests {
+    use super::{NarrowingSqrt, SqrtInto};
+    use IntegerSquareRoot;
+
+    #[test]
+    fn narrows() {
+        let root: u16 = u32::MAX.sqrt_into();
+        assert_eq!(root, u16::MAX);
+        let root: u64 = i128::MAX.sqrt_into();
+        assert_eq!(u128::from(root), i128::MAX.integer_sqrt() as u128);
+        let root: u8 = i16::MAX.sqrt_into();
+        assert_eq!(root, 181);
+        let root: u64 = usize::MAX.sqrt_into();
+        assert_eq!(root, usize::MAX.integer_sqrt() as u64);
+        for n in i16::MIN..=i16::MAX {
+            let root: Option<i32> = n.sqrt_into_checked();
+            assert_eq!(root, n.integer_sqrt_checked().map(i32::from), "in {}", n);
+        }
+    }
+
+    #[test]
+    fn narrowing() {
+        let root: u8 = u16::MAX.narrowing_sqrt();
+        assert_eq!(root, u8::MAX);
+        assert_eq!(i8::MAX.narrowing_sqrt(), 11u8);
+        assert_eq!(i64::MAX.narrowing_sqrt(), 3_037_000_499u32);
+        for n in 0..=u32::from(u16::MAX) {
+            assert_eq!(u32::from(n.narrowing_sqrt()), n.integer_sqrt(), "in {}", n);
+        }
+        assert_eq!(i128::MIN.narrowing_sqrt_checked(), None);
+    }
+}
@@ -0,0 +1,58 @@
+use core::arch::aarch64::*;
+
+use sqrt_non_negative;
+
+// The roots are estimated with the vector float square root and corrected as in `float_sqrt`, on
+// whole vectors at once. A true comparison gives a lane of all ones, which is -1, so adding the
+// mask subtracts 1 from the lanes where it holds.
+
+/// Replace every `u32` in `values` with its integer square root, four at a time.
+pub(crate) fn sqrt_slice_u32(values: &mut [u32]) {
+    let mut chunks = values.chunks_exact_mut(4);
+    for chunk in &mut chunks {
+        unsafe {
+            let n = vld1q_u32(chunk.as_ptr());
+            let estimate = vcvtq_u32_f32(vsqrtq_f32(vcvtq_f32_u32(n)));
+            // The estimate is at most 2^16, whose square wraps to 0, so that overshoots too
+            let overshoots = vorrq_u32(
+                vtstq_u32(estimate, vdupq_n_u32(0xffff_0000)),
+                vcgtq_u32(vmulq_u32(estimate, estimate), n),
+            );
+            let root = vaddq_u32(estimate, overshoots);
+            let rem = vsubq_u32(n, vmulq_u32(root, root));
+            let undershoots = vcgtq_u32(rem, vaddq_u32(root, root));
+            vst1q_u32(chunk.as_mut_ptr(), vsubq_u32(root, undershoots));
+        }
+    }
+    for value in chunks.into_remainder() {
+        *value = sqrt_non_negative(*value);
+    }
+}
+
+/// Replace every `u64` in `values` with its integer square root, two at a time.
+///
+/// NEON has no 64 bit multiply, but the roots fit in 32 bits, so they are squared with the
+/// widening 32 bit multiply.
+pub(crate) fn sqrt_slice_u64(values: &mut [u64]) {
+    let mut chunks = values.chunks_exact_mut(2);
+    for chunk in &mut chunks {
+        unsafe {
+            let n = vld1q_u64(chunk.as_ptr());
+            let estimate = vcvtq_u64_f64(vsqrtq_f64(vcvtq_f64_u64(n)));
+            // The estimate is at most 2^32, which narrows to 0, so that overshoots too
+            let low = vmovn_u64(estimate);
+            let overshoots = vorrq_u64(
+                vtstq_u64(estimate, vdupq_n_u64(0xffff_ffff_0000_0000)),
+                vcgtq_u64(vmull_u32(low, low), n),
+            );
+            let root = vaddq_u64(estimate, overshoots);
+            let low = vmovn_u64(root);
+            let rem = vsubq_u64(n, vmull_u32(low, low));
+            let undershoots = vcgtq_u64(rem, vaddq_u64(root, root));
+            vst1q_u64(chunk.as_mut_ptr(), vsubq_u64(root, undershoots));
+        }
+    }
+    for value in chunks.into_remainder() {
+        *value = sqrt_non_negative(*value);
+    }
+}
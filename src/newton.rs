@@ -0,0 +1,163 @@
+use core::iter::FusedIterator;
+
+use primitive::Primitive;
+
+/// Iterate over the successive guesses of Newton's (or Heron's) method for the integer square
+/// root of `n`.
+///
+/// The first guess is a power of two no smaller than the root, and each guess is improved to
+/// `(x + n / x) / 2` until that stops decreasing. The last guess is the integer square root.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::newton_steps;
+///
+/// # fn main() {
+/// let steps: Vec<u32> = newton_steps(1000u32).collect();
+/// assert_eq!(steps, [32, 31]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// For negative numbers (`i` family) this function will panic on negative input
+pub fn newton_steps<T: Primitive>(n: T) -> NewtonSteps<T> {
+    newton_steps_checked(n).expect("cannot calculate square root of negative number")
+}
+
+/// Iterate over the successive guesses of Newton's method for the integer square root of `n`,
+/// returning `None` if it is negative (this can never happen for unsigned types).
+pub fn newton_steps_checked<T: Primitive>(n: T) -> Option<NewtonSteps<T>> {
+    if n < T::zero() {
+        return None;
+    }
+    Some(NewtonSteps {
+        n,
+        next: Some(first_guess(n)),
+    })
+}
+
+/// The first guess of Newton's method for the root of non-negative `n`: the smallest power of two
+/// that is no smaller than the root, or 0 for 0.
+pub(crate) fn first_guess<T: Primitive>(n: T) -> T {
+    if n == T::zero() {
+        T::zero()
+    } else {
+        // n < 2^bits, so sqrt(n) < 2^ceil(bits / 2)
+        let bits = T::zero().leading_zeros() - n.leading_zeros();
+        T::one().unsigned_shl(bits.div_ceil(2))
+    }
+}
+
+/// Find the integer square root of non-negative `n` by Newton's method.
+///
+/// The first guess is within a factor of 2 of the root and the error is squared at every step,
+/// so this takes about 6 steps for 64 bit numbers and 7 for 128 bit ones, rather than one per
+/// bit of the root. Each step is a division, which makes it worth it only for the wide types.
+pub(crate) fn newton_sqrt<T: Primitive>(n: T) -> T {
+    newton_sqrt_from(n, first_guess(n))
+}
+
+/// Find the integer square root of non-negative `n` by Newton's method, starting from a guess
+/// that is no smaller than the root, and no larger than `2^ceil(bits / 2)`.
+pub(crate) fn newton_sqrt_from<T: Primitive>(n: T, mut guess: T) -> T {
+    if guess == T::zero() {
+        return guess;
+    }
+    // The guesses decrease to the root and then stop decreasing. The guess is at most
+    // 2^ceil(bits / 2) and `n / guess` is less than it, so the sum cannot overflow.
+    loop {
+        let improved = (guess + n / guess).unsigned_shr(1);
+        if improved >= guess {
+            return guess;
+        }
+        guess = improved;
+    }
+}
+
+/// An iterator over the guesses of Newton's method for an integer square root, created by
+/// [`newton_steps`].
+///
+/// [`newton_steps`]: ./fn.newton_steps.html
+#[derive(Debug, Clone)]
+pub struct NewtonSteps<T> {
+    n: T,
+    /// The next guess to yield, or `None` once the root has been yielded
+    next: Option<T>,
+}
+
+impl<T: Primitive> Iterator for NewtonSteps<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let guess = self.next?;
+        self.next = if guess == T::zero() {
+            None
+        } else {
+            let improved = (guess + self.n / guess).unsigned_shr(1);
+            if improved < guess {
+                Some(improved)
+            } else {
+                None
+            }
+        };
+        Some(guess)
+    }
+}
+
+impl<T: Primitive> FusedIterator for NewtonSteps<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::{newton_sqrt, newton_steps, newton_steps_checked};
+    use IntegerSquareRoot;
+
+    #[test]
+    fn converges() {
+        for n in 0..=u16::MAX {
+            let mut previous = None;
+            for guess in newton_steps(n) {
+                assert!(previous.map_or(true, |p| guess < p), "in {}", n);
+                assert!(guess >= n.integer_sqrt(), "in {}", n);
+                previous = Some(guess);
+            }
+            assert_eq!(previous, Some(n.integer_sqrt()), "in {}", n);
+        }
+        for &n in [u64::MAX, u64::MAX - 1, 1 << 63].iter() {
+            assert_eq!(newton_steps(n).last(), Some(n.integer_sqrt()));
+        }
+        assert_eq!(
+            newton_steps(u128::MAX).last(),
+            Some(u128::MAX.integer_sqrt())
+        );
+        assert_eq!(newton_steps(i8::MAX).last(), Some(11));
+        assert!(newton_steps(i128::MAX).count() < 10);
+    }
+
+    #[test]
+    fn negative() {
+        assert!(newton_steps_checked(-1i32).is_none());
+    }
+
+    #[test]
+    fn newton_sqrt_test() {
+        for n in 0..=u16::MAX {
+            assert_eq!(newton_sqrt(n), n.integer_sqrt(), "in {}", n);
+            assert_eq!(newton_sqrt(u64::from(n)), u64::from(n.integer_sqrt()));
+        }
+        for shift in 0..128 {
+            for &n in [1u128 << shift, (1 << shift) - 1, (1 << shift) + 1].iter() {
+                let root = newton_sqrt(n);
+                assert!(root * root <= n, "in {}", n);
+                assert!(
+                    (root + 1).checked_mul(root + 1).map_or(true, |s| s > n),
+                    "in {}",
+                    n
+                );
+            }
+        }
+        assert_eq!(newton_sqrt(u128::MAX), u64::MAX as u128);
+        assert_eq!(newton_sqrt(i128::MAX), 13_043_817_825_332_782_212);
+        assert_eq!(newton_sqrt(u64::MAX), u32::MAX as u64);
+    }
+}
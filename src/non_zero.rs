@@ -0,0 +1,142 @@
+use core::num::{NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize};
+
+use IntegerSquareRoot;
+
+/// A trait implementing integer square root for the non-zero unsigned types.
+///
+/// The square root of a non-zero unsigned number is itself non-zero, so unlike
+/// [`IntegerSquareRoot`] the roots keep the non-zero type and none of the methods can fail. The
+/// remainder can be zero, so it is returned as the underlying integer type.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::NonZeroSquareRoot;
+/// use std::num::NonZeroU32;
+///
+/// # fn main() {
+/// let n = NonZeroU32::new(10).unwrap();
+/// assert_eq!(n.integer_sqrt(), NonZeroU32::new(3).unwrap());
+/// assert_eq!(n.integer_sqrt_rem(), (NonZeroU32::new(3).unwrap(), 1));
+/// # }
+/// ```
+///
+/// [`IntegerSquareRoot`]: ./trait.IntegerSquareRoot.html
+pub trait NonZeroSquareRoot {
+    /// The underlying integer type.
+    type Int;
+
+    /// Find the integer square root, rounded down.
+    fn integer_sqrt(&self) -> Self
+    where
+        Self: Sized;
+
+    /// Find the integer square root, rounded up.
+    fn integer_sqrt_ceil(&self) -> Self
+    where
+        Self: Sized;
+
+    /// Find the integer square root, rounded to the nearest integer.
+    fn integer_sqrt_round(&self) -> Self
+    where
+        Self: Sized;
+
+    /// Find the integer square root (rounded down) and the remainder `self - root * root`.
+    fn integer_sqrt_rem(&self) -> (Self, Self::Int)
+    where
+        Self: Sized;
+
+    /// Find the square root of a perfect square, returning `None` if the number is not a perfect
+    /// square.
+    fn exact_sqrt(&self) -> Option<Self>
+    where
+        Self: Sized;
+}
+
+macro_rules! impl_non_zero {
+    ($($type:ty => $int:ty),*) => {
+        $(
+            impl NonZeroSquareRoot for $type {
+                type Int = $int;
+
+                #[inline]
+                fn integer_sqrt(&self) -> Self {
+                    Self::new(self.get().integer_sqrt()).unwrap()
+                }
+
+                #[inline]
+                fn integer_sqrt_ceil(&self) -> Self {
+                    Self::new(self.get().integer_sqrt_ceil()).unwrap()
+                }
+
+                #[inline]
+                fn integer_sqrt_round(&self) -> Self {
+                    Self::new(self.get().integer_sqrt_round()).unwrap()
+                }
+
+                #[inline]
+                fn integer_sqrt_rem(&self) -> (Self, $int) {
+                    let (root, rem) = self.get().integer_sqrt_rem();
+                    (Self::new(root).unwrap(), rem)
+                }
+
+                #[inline]
+                fn exact_sqrt(&self) -> Option<Self> {
+                    self.get().exact_sqrt().and_then(Self::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_non_zero! {
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroUsize => usize
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::{NonZeroU128, NonZeroU16, NonZeroU8};
+
+    use super::NonZeroSquareRoot;
+    use IntegerSquareRoot;
+
+    #[test]
+    fn exhaustive_u16() {
+        for n in 1..=u16::MAX {
+            let nz = NonZeroU16::new(n).unwrap();
+            assert_eq!(nz.integer_sqrt().get(), n.integer_sqrt(), "in {}", n);
+            assert_eq!(
+                nz.integer_sqrt_ceil().get(),
+                n.integer_sqrt_ceil(),
+                "in {}",
+                n
+            );
+            assert_eq!(
+                nz.integer_sqrt_round().get(),
+                n.integer_sqrt_round(),
+                "in {}",
+                n
+            );
+            let (root, rem) = nz.integer_sqrt_rem();
+            assert_eq!((root.get(), rem), n.integer_sqrt_rem(), "in {}", n);
+            assert_eq!(nz.exact_sqrt().map(|r| r.get()), n.exact_sqrt(), "in {}", n);
+        }
+    }
+
+    #[test]
+    fn extremes() {
+        assert_eq!(NonZeroU8::new(1).unwrap().integer_sqrt().get(), 1);
+        assert_eq!(
+            NonZeroU8::new(u8::MAX).unwrap().integer_sqrt_ceil().get(),
+            16
+        );
+        assert_eq!(
+            NonZeroU128::new(u128::MAX).unwrap().integer_sqrt().get(),
+            u128::MAX.integer_sqrt()
+        );
+    }
+}
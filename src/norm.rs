@@ -0,0 +1,212 @@
+use wide::{sqrt_sum_of_squares, Hypot, Magnitude};
+
+/// Find the integer length of the hypotenuse of a right-angled triangle with sides `a` and `b`,
+/// i.e. the integer square root of `a * a + b * b`.
+///
+/// The sum is computed at twice the width of the result, so this never overflows. The result is
+/// returned as the next wider unsigned type for the `u` family, since `sqrt(2) * T::MAX` does
+/// not fit in `T`, and as the unsigned type of the same width for the `i` family, which always
+/// holds it. `u128` has no wider type, so [`sqrt_of_sum_of_squares_checked`] takes its place.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::integer_hypot;
+///
+/// # fn main() {
+/// assert_eq!(integer_hypot(3u32, 4), 5u64);
+/// assert_eq!(integer_hypot(255u8, 255), 360u16);
+/// assert_eq!(integer_hypot(-128i8, -128), 181u8);
+/// # }
+/// ```
+///
+/// [`sqrt_of_sum_of_squares_checked`]: ./fn.sqrt_of_sum_of_squares_checked.html
+#[inline]
+pub fn integer_hypot<T: Hypot>(a: T, b: T) -> T::Length {
+    sqrt_sum_of_squares([a.length(), b.length()].iter().cloned())
+        .expect("the length of two values always fits")
+}
+
+/// Find the integer length of the 3D vector `(a, b, c)`, i.e. the integer square root of
+/// `a * a + b * b + c * c`.
+///
+/// Like [`integer_hypot`], this never overflows, and the result is returned as the next wider
+/// unsigned type for the `u` family and the unsigned type of the same width for the `i` family.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::integer_hypot3;
+///
+/// # fn main() {
+/// assert_eq!(integer_hypot3(2u32, 3, 6), 7u64);
+/// assert_eq!(integer_hypot3(255u8, 255, 255), 441u16);
+/// assert_eq!(integer_hypot3(-128i8, -128, -128), 221u8);
+/// # }
+/// ```
+///
+/// [`integer_hypot`]: ./fn.integer_hypot.html
+#[inline]
+pub fn integer_hypot3<T: Hypot>(a: T, b: T, c: T) -> T::Length {
+    sqrt_sum_of_squares([a.length(), b.length(), c.length()].iter().cloned())
+        .expect("the length of three values always fits")
+}
+
+/// Find the integer length (Euclidean norm) of the vector `values`, i.e. the integer square root
+/// of the sum of their squares.
+///
+/// The result is returned as the next wider unsigned type, [`Magnitude::Wide`], and the sum is
+/// accumulated at twice its width. Up to `4^bits` values of a `bits` wide type always fit, so
+/// for all but the 128 bit types this cannot overflow for any slice that fits in memory. The
+/// 128 bit types have no wider type, so their lengths are `u128`s, which hold the length of up
+/// to three `i128`s.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::sqrt_of_sum_of_squares;
+///
+/// # fn main() {
+/// assert_eq!(sqrt_of_sum_of_squares(&[1i16, -2, 2, -4]), 5u32);
+/// assert_eq!(sqrt_of_sum_of_squares(&[255u8, 255]), 360u16);
+/// assert_eq!(sqrt_of_sum_of_squares::<u8>(&[]), 0);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If the sum of the squares overflows, which can only happen for more than `4^bits` values, or
+/// if the result does not fit in `u128` for the 128 bit types.
+///
+/// [`Magnitude::Wide`]: ./trait.Magnitude.html#associatedtype.Wide
+#[inline]
+pub fn sqrt_of_sum_of_squares<T: Magnitude>(values: &[T]) -> T::Wide {
+    sqrt_of_sum_of_squares_checked(values).expect("integer norm overflowed")
+}
+
+/// Find the integer length (Euclidean norm) of the vector `values`, returning `None` if the sum
+/// of the squares overflows or the result does not fit in [`Magnitude::Wide`].
+///
+/// [`Magnitude::Wide`]: ./trait.Magnitude.html#associatedtype.Wide
+#[inline]
+pub fn sqrt_of_sum_of_squares_checked<T: Magnitude>(values: &[T]) -> Option<T::Wide> {
+    sqrt_sum_of_squares(values.iter().map(|&value| value.wide_magnitude()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        integer_hypot, integer_hypot3, sqrt_of_sum_of_squares, sqrt_of_sum_of_squares_checked,
+    };
+    use std::vec;
+    use IntegerSquareRoot;
+
+    #[test]
+    fn hypot() {
+        for a in i8::MIN..=i8::MAX {
+            for b in i8::MIN..=i8::MAX {
+                let sum = i32::from(a) * i32::from(a) + i32::from(b) * i32::from(b);
+                assert_eq!(
+                    i32::from(integer_hypot(a, b)),
+                    sum.integer_sqrt(),
+                    "{} {}",
+                    a,
+                    b
+                );
+            }
+        }
+        for a in 0..=u8::MAX {
+            for b in 0..=u8::MAX {
+                let root =
+                    (u32::from(a) * u32::from(a) + u32::from(b) * u32::from(b)).integer_sqrt();
+                assert_eq!(u32::from(integer_hypot(a, b)), root, "{} {}", a, b);
+            }
+        }
+        assert_eq!(integer_hypot(3u64 << 60, 4 << 60), 5 << 60);
+        assert_eq!(
+            integer_hypot(i128::MIN, i128::MIN),
+            240615969168004511545033772477625056927
+        );
+        assert_eq!(
+            integer_hypot(i128::MAX, i128::MAX),
+            240615969168004511545033772477625056925
+        );
+        assert_eq!(integer_hypot(isize::MIN, 0), isize::MIN.unsigned_abs());
+        // The largest lengths, which need one bit more than the unsigned types
+        assert_eq!(integer_hypot(u8::MAX, u8::MAX), 360);
+        assert_eq!(integer_hypot(u32::MAX, u32::MAX), 6_074_000_998);
+        assert_eq!(
+            integer_hypot(u64::MAX, u64::MAX),
+            26_087_635_650_665_564_423
+        );
+        assert_eq!(integer_hypot(u64::MAX, 0), u128::from(u64::MAX));
+    }
+
+    #[test]
+    fn hypot3() {
+        for a in (i8::MIN..=i8::MAX).step_by(3) {
+            for b in (i8::MIN..=i8::MAX).step_by(5) {
+                for c in i8::MIN..=i8::MAX {
+                    let square = |n: i8| i32::from(n) * i32::from(n);
+                    assert_eq!(
+                        i32::from(integer_hypot3(a, b, c)),
+                        (square(a) + square(b) + square(c)).integer_sqrt(),
+                        "{} {} {}",
+                        a,
+                        b,
+                        c
+                    );
+                }
+            }
+        }
+        assert_eq!(integer_hypot3(1u8, 4, 8), 9);
+        assert_eq!(integer_hypot3(200u8, 200, 0), 282);
+        assert_eq!(integer_hypot3(u8::MAX, u8::MAX, u8::MAX), 441);
+        assert_eq!(integer_hypot3(u64::MAX, 0, 0), u128::from(u64::MAX));
+        assert_eq!(
+            integer_hypot3(u64::MAX, u64::MAX, u64::MAX),
+            31_950_697_969_885_030_201
+        );
+        assert_eq!(
+            integer_hypot3(i64::MIN, i64::MIN, i64::MIN),
+            15975348984942515101
+        );
+        assert_eq!(integer_hypot3(2i128 << 120, 3 << 120, 6 << 120), 7 << 120);
+    }
+
+    #[test]
+    fn slice_norm() {
+        let values: [i8; 8] = [3, -7, 0, 127, -128, 55, -1, 12];
+        for len in 0..=values.len() {
+            let values = &values[..len];
+            let sum: i32 = values.iter().map(|&n| i32::from(n) * i32::from(n)).sum();
+            assert_eq!(
+                i32::from(sqrt_of_sum_of_squares(values)),
+                sum.integer_sqrt(),
+                "len {}",
+                len
+            );
+        }
+        // Short slices at the limits of the same width unsigned types
+        assert_eq!(sqrt_of_sum_of_squares(&[u8::MAX, u8::MAX]), 360);
+        assert_eq!(sqrt_of_sum_of_squares(&[i8::MIN; 4]), 256);
+        assert_eq!(sqrt_of_sum_of_squares(&[i32::MIN; 3]), 3719550786);
+        assert_eq!(sqrt_of_sum_of_squares(&[i32::MIN; 4]), 1 << 32);
+        assert_eq!(
+            sqrt_of_sum_of_squares(&[u64::MAX; 4]),
+            2 * u128::from(u64::MAX)
+        );
+        assert_eq!(
+            sqrt_of_sum_of_squares(&[usize::MAX; 9]),
+            3 * usize::MAX as u128
+        );
+        // 4^8 values of a u8 always fit, and the sum of 66052 squares of 255 overflows 32 bits
+        assert_eq!(sqrt_of_sum_of_squares(&vec![u8::MAX; 1 << 16]), 256 * 255);
+        assert_eq!(sqrt_of_sum_of_squares_checked(&vec![u8::MAX; 66_052]), None);
+        assert_eq!(
+            sqrt_of_sum_of_squares(&[i128::MIN; 3]),
+            integer_hypot3(i128::MIN, i128::MIN, i128::MIN)
+        );
+        assert_eq!(sqrt_of_sum_of_squares_checked(&[i128::MIN; 4]), None);
+        assert_eq!(sqrt_of_sum_of_squares(&[u128::MAX]), u128::MAX);
+        assert_eq!(sqrt_of_sum_of_squares_checked(&[u128::MAX, 1 << 65]), None);
+        assert_eq!(sqrt_of_sum_of_squares(&[6u64 << 40; 4]), 12 << 40);
+    }
+}
@@ -0,0 +1,368 @@
+use cbrt::cbrt_non_negative;
+use primitive::Primitive;
+use sqrt_non_negative;
+use IntegerSquareRoot;
+
+/// A trait implementing integer nth root for a degree chosen at runtime.
+pub trait IntegerNthRoot {
+    /// Find the integer `n`th root, truncated toward zero.
+    ///
+    /// For non-negative numbers this is the largest `r` such that `r.pow(n) <= self`. Negative
+    /// numbers (`i` family) have a negative root when `n` is odd, which is also truncated toward
+    /// zero, so `(-28).nth_root(3)` is `-3`.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is zero, or for negative numbers (`i` family) when `n` is even.
+    fn nth_root(&self, n: u32) -> Self
+    where
+        Self: Sized,
+    {
+        assert!(n != 0, "cannot calculate the zeroth root of a number");
+        self.nth_root_checked(n)
+            .expect("cannot calculate even root of negative number")
+    }
+
+    /// Find the integer `n`th root, returning `None` if `n` is zero or if the number is negative
+    /// and `n` is even (the latter can never happen for unsigned types).
+    fn nth_root_checked(&self, n: u32) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Detect whether the number is a perfect power, returning `Some((base, exponent))` such that
+    /// `base.pow(exponent) == self` and `exponent >= 2`.
+    ///
+    /// When there is more than one way of writing the number as a power, the largest exponent is
+    /// returned, so 64 gives `(2, 6)` rather than `(8, 2)` or `(4, 3)`. Negative numbers (`i`
+    /// family) are powers of a negative base with an odd exponent, so -64 gives `(-4, 3)`.
+    ///
+    /// 0, 1 and -1 are powers with every exponent, so there is no meaningful answer for them and
+    /// `None` is returned.
+    fn is_perfect_power(&self) -> Option<(Self, u32)>
+    where
+        Self: Sized;
+
+    /// Find the integer `N`th root, for a degree known at compile time.
+    ///
+    /// For the primitive types the degree is matched when the method is instantiated, so
+    /// `nth_root_const::<2>()` calls the square root directly, `nth_root_const::<3>()` the cube
+    /// root and `nth_root_const::<4>()` two square roots, with no dispatch at runtime. Other
+    /// degrees use the same method as [`nth_root`]. A degree of zero is rejected at compile time.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) when `N` is even.
+    ///
+    /// [`nth_root`]: #method.nth_root
+    #[inline]
+    fn nth_root_const<const N: u32>(&self) -> Self
+    where
+        Self: Sized,
+    {
+        self.nth_root_const_checked::<N>()
+            .expect("cannot calculate even root of negative number")
+    }
+
+    /// Find the integer `N`th root for a degree known at compile time, returning `None` if the
+    /// number is negative and `N` is even (this can never happen for unsigned types).
+    #[inline]
+    fn nth_root_const_checked<const N: u32>(&self) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        #[allow(clippy::let_unit_value)]
+        let () = NonZeroDegree::<N>::CHECK;
+        self.nth_root_checked(N)
+    }
+}
+
+/// Rejects a degree of zero for `nth_root_const` when it is instantiated, by failing to evaluate
+/// `CHECK`.
+struct NonZeroDegree<const N: u32>;
+
+impl<const N: u32> NonZeroDegree<N> {
+    const CHECK: () = assert!(N != 0, "cannot calculate the zeroth root of a number");
+}
+
+impl<T: Primitive> IntegerNthRoot for T {
+    #[inline]
+    fn nth_root_checked(&self, n: u32) -> Option<Self> {
+        if n == 0 {
+            None
+        } else if *self >= T::zero() {
+            Some(nth_root_non_negative(*self, n))
+        } else if n % 2 == 0 {
+            None
+        } else {
+            Some(odd_root_of_negative(*self, n, |m| {
+                nth_root_non_negative(m, n)
+            }))
+        }
+    }
+
+    #[inline]
+    fn nth_root_const_checked<const N: u32>(&self) -> Option<Self> {
+        #[allow(clippy::let_unit_value)]
+        let () = NonZeroDegree::<N>::CHECK;
+        if *self >= T::zero() {
+            Some(nth_root_const_non_negative::<T, N>(*self))
+        } else if N % 2 == 0 {
+            None
+        } else {
+            Some(odd_root_of_negative(
+                *self,
+                N,
+                nth_root_const_non_negative::<T, N>,
+            ))
+        }
+    }
+
+    fn is_perfect_power(&self) -> Option<(Self, u32)> {
+        if *self <= T::one() && *self >= T::zero() - T::one() {
+            return None;
+        }
+
+        // An exponent greater than the bit length of |self| would need a base smaller than 2, so
+        // only exponents up to that bound are tried, largest first. For negative numbers the
+        // bound is computed from -(self + 1), which cannot overflow, plus one.
+        let negative = *self < T::zero();
+        let magnitude = if negative {
+            T::zero() - (*self + T::one())
+        } else {
+            *self
+        };
+        let bits = T::zero().leading_zeros() - magnitude.leading_zeros();
+        let max_exponent = if negative { bits + 1 } else { bits - 1 };
+        (2..=max_exponent)
+            .rev()
+            .filter(|exponent| !negative || exponent % 2 != 0)
+            .filter_map(|exponent| {
+                let base = self.nth_root_checked(exponent)?;
+                if num_traits::checked_pow(base, exponent as usize) == Some(*self) {
+                    Some((base, exponent))
+                } else {
+                    None
+                }
+            })
+            .next()
+    }
+}
+
+/// The integer `N`th root of a non-negative number, for `N >= 1`.
+///
+/// `N` is a constant, so only one arm of the match is kept.
+#[inline]
+fn nth_root_const_non_negative<T: Primitive, const N: u32>(x: T) -> T {
+    match N {
+        1 => x,
+        2 => sqrt_non_negative(x),
+        3 => cbrt_non_negative(x),
+        4 => sqrt_non_negative(sqrt_non_negative(x)),
+        _ => nth_root_non_negative(x, N),
+    }
+}
+
+/// The integer `n`th root of a non-negative number, for `n >= 1`.
+fn nth_root_non_negative<T: Primitive>(x: T, n: u32) -> T {
+    match n {
+        1 => return x,
+        2 => return x.integer_sqrt(),
+        3 => return cbrt_non_negative(x),
+        4 => return x.integer_fourth_root(),
+        _ => {}
+    }
+    if x == T::zero() {
+        return T::zero();
+    }
+
+    // The root of a number with `high_bit + 1` significant bits has at most
+    // `high_bit / n + 1` bits, so decide them one at a time from the top, keeping each bit
+    // whose inclusion leaves `result^n <= x`. Overflow of the power means "too big".
+    let high_bit: u32 = T::zero().leading_zeros() - x.leading_zeros() - 1;
+    let mut bit = T::one().unsigned_shl(high_bit / n);
+    let mut result = T::zero();
+    while bit != T::zero() {
+        let candidate = result | bit;
+        match num_traits::checked_pow(candidate, n as usize) {
+            Some(power) if power <= x => result = candidate,
+            _ => {}
+        }
+        bit = bit.unsigned_shr(1);
+    }
+    result
+}
+
+/// The odd `n`th root of a negative number, rounded towards zero.
+///
+/// `root` must compute the `n`th root of a non-negative number. It is applied to `-(x + 1)`
+/// rather than `-x` so that `T::min_value()` can be handled without overflow, and the result is
+/// then corrected for the case where `-x` is itself a perfect power.
+pub(crate) fn odd_root_of_negative<T, F>(x: T, n: u32, root: F) -> T
+where
+    T: Primitive,
+    F: FnOnce(T) -> T,
+{
+    debug_assert!(x < T::zero() && n % 2 != 0);
+    let r = root(T::zero() - (x + T::one()));
+    let next = T::zero() - r - T::one();
+    if num_traits::checked_pow(next, n as usize) == Some(x) {
+        next
+    } else {
+        T::zero() - r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IntegerNthRoot;
+
+    macro_rules! gen_tests {
+        ($($type:ty => $fn_name:ident),*) => {
+            $(
+                #[test]
+                fn $fn_name() {
+                    let is_nth_root_of = |root: $type, n: $type, k: u32| {
+                        root.checked_pow(k).map_or(false, |p| p <= n)
+                            && root
+                                .checked_add(1)
+                                .and_then(|r| r.checked_pow(k))
+                                .map_or(true, |p| p > n)
+                    };
+                    let tests: [($type, u32, $type); 10] = [
+                        (0, 4, 0),
+                        (1, 5, 1),
+                        (2, 1, 2),
+                        (15, 4, 1),
+                        (16, 4, 2),
+                        (80, 4, 2),
+                        (81, 4, 3),
+                        (31, 5, 1),
+                        (32, 5, 2),
+                        (100, 7, 1),
+                    ];
+                    for &(in_, k, out) in tests.iter() {
+                        assert_eq!(in_.nth_root(k), out, "in {} k {}", in_, k);
+                    }
+                    for k in 1..=<$type>::BITS + 1 {
+                        for &in_ in [<$type>::MAX, <$type>::MAX - 1, <$type>::MAX / 3].iter() {
+                            assert!(is_nth_root_of(in_.nth_root(k), in_, k), "in {} k {}", in_, k);
+                        }
+                    }
+                }
+            )*
+        };
+    }
+
+    gen_tests! {
+        i8 => i8_test,
+        u8 => u8_test,
+        i16 => i16_test,
+        u16 => u16_test,
+        i32 => i32_test,
+        u32 => u32_test,
+        i64 => i64_test,
+        u64 => u64_test,
+        i128 => i128_test,
+        u128 => u128_test,
+        isize => isize_test,
+        usize => usize_test
+    }
+
+    #[test]
+    fn const_degree() {
+        use {IntegerCubeRoot, IntegerSquareRoot};
+        for n in 0..=u16::MAX {
+            assert_eq!(n.nth_root_const::<1>(), n);
+            assert_eq!(n.nth_root_const::<2>(), n.integer_sqrt());
+            assert_eq!(n.nth_root_const::<3>(), n.integer_cbrt());
+            assert_eq!(n.nth_root_const::<4>(), n.nth_root(4));
+            assert_eq!(n.nth_root_const::<5>(), n.nth_root(5));
+        }
+        for n in i16::MIN..=i16::MAX {
+            assert_eq!(n.nth_root_const_checked::<2>(), n.nth_root_checked(2));
+            assert_eq!(n.nth_root_const_checked::<3>(), n.nth_root_checked(3));
+            assert_eq!(n.nth_root_const_checked::<4>(), n.nth_root_checked(4));
+            assert_eq!(n.nth_root_const_checked::<7>(), n.nth_root_checked(7));
+        }
+        assert_eq!((-8i32).nth_root_const_checked::<4>(), None);
+    }
+
+    #[test]
+    fn perfect_power() {
+        let tests: [(i64, Option<(i64, u32)>); 13] = [
+            (-1, None),
+            (0, None),
+            (1, None),
+            (2, None),
+            (4, Some((2, 2))),
+            (64, Some((2, 6))),
+            (-64, Some((-4, 3))),
+            (-8, Some((-2, 3))),
+            (-4, None),
+            (36, Some((6, 2))),
+            (1 << 62, Some((2, 62))),
+            (i64::MIN, Some((-2, 63))),
+            (i64::MAX, None),
+        ];
+        for &(in_, out) in tests.iter() {
+            assert_eq!(in_.is_perfect_power(), out, "in {}", in_);
+        }
+        assert_eq!(i8::MIN.is_perfect_power(), Some((-2, 7)));
+        assert_eq!(243u8.is_perfect_power(), Some((3, 5)));
+        assert_eq!(u128::MAX.is_perfect_power(), None);
+        assert_eq!((1u128 << 127).is_perfect_power(), Some((2, 127)));
+        assert_eq!(3u128.pow(80).is_perfect_power(), Some((3, 80)));
+        // Enumerate every power in range, keeping the largest exponent (and positive base)
+        let mut expected = [None; 1 << 16];
+        for k in 2..16 {
+            for b in -182i16..=182 {
+                if let Some(n) = b.checked_pow(k) {
+                    if !(-1..=1).contains(&n) && (b > 0 || n < 0) {
+                        expected[n as u16 as usize] = Some((b, k));
+                    }
+                }
+            }
+        }
+        for n in i16::MIN..=i16::MAX {
+            assert_eq!(
+                n.is_perfect_power(),
+                expected[n as u16 as usize],
+                "in {}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn zeroth_root() {
+        assert_eq!(10u32.nth_root_checked(0), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zeroth_root_panics() {
+        10u32.nth_root(0);
+    }
+
+    #[test]
+    fn negative() {
+        for k in (2..10).step_by(2) {
+            assert_eq!((-1i32).nth_root_checked(k), None);
+        }
+        for k in (1..10).step_by(2) {
+            assert_eq!((-1i32).nth_root_checked(k), Some(-1));
+        }
+        assert_eq!((-32i32).nth_root(5), -2);
+        assert_eq!((-33i32).nth_root(5), -2);
+        assert_eq!((-31i32).nth_root(5), -1);
+        assert_eq!(i8::MIN.nth_root(7), -2);
+        assert_eq!(i64::MIN.nth_root(63), -2);
+        assert_eq!(i64::MIN.nth_root(9), -128);
+        for k in (1..=17).step_by(2) {
+            for n in i16::MIN..0 {
+                let expected = -(-i32::from(n)).nth_root(k) as i16;
+                assert_eq!(n.nth_root(k), expected, "in {} k {}", n, k);
+            }
+        }
+    }
+}
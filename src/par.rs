@@ -0,0 +1,149 @@
+use rayon::iter::Map;
+use rayon::prelude::*;
+
+use primitive::Primitive;
+use IntegerSquareRoot;
+
+/// How many elements each thread roots at a time. Large enough that the vectorised kernels run
+/// over whole vectors and the cost of splitting the work is small.
+const CHUNK_LEN: usize = 1 << 14;
+
+/// Replace every element of `values` with its integer square root, across the threads of the
+/// rayon pool. This needs the `rayon` feature.
+///
+/// Each thread roots its part of the slice in place, with the vectorised kernels where the target
+/// has them, as [`IntegerSqrtAssign`] does on a single thread.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::par_integer_sqrt_slice;
+///
+/// # fn main() {
+/// let mut values: Vec<u64> = (0..100_000).collect();
+/// par_integer_sqrt_slice(&mut values);
+/// assert_eq!(values[99_999], 316);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If any element is negative (`i` family), in which case no element is changed
+///
+/// [`IntegerSqrtAssign`]: ./trait.IntegerSqrtAssign.html
+pub fn par_integer_sqrt_slice<T: Primitive + Send + Sync>(values: &mut [T]) {
+    assert!(
+        par_integer_sqrt_slice_checked(values),
+        "cannot calculate square root of negative number"
+    );
+}
+
+/// Replace every element of `values` with its integer square root across threads, returning
+/// `false` and leaving the whole slice unchanged if any element is negative.
+pub fn par_integer_sqrt_slice_checked<T: Primitive + Send + Sync>(values: &mut [T]) -> bool {
+    if values.par_iter().any(|value| *value < T::zero()) {
+        return false;
+    }
+    values.par_chunks_mut(CHUNK_LEN).for_each(T::sqrt_slice);
+    true
+}
+
+/// The parallel iterator returned by [`ParallelSqrtIterator::map_integer_sqrt`].
+///
+/// [`ParallelSqrtIterator::map_integer_sqrt`]: ./trait.ParallelSqrtIterator.html#method.map_integer_sqrt
+pub type ParSqrtMap<I> = Map<I, fn(<I as ParallelIterator>::Item) -> <I as ParallelIterator>::Item>;
+
+/// The parallel iterator returned by [`ParallelSqrtIterator::map_integer_sqrt_checked`].
+///
+/// [`ParallelSqrtIterator::map_integer_sqrt_checked`]: ./trait.ParallelSqrtIterator.html#method.map_integer_sqrt_checked
+pub type ParSqrtCheckedMap<I> =
+    Map<I, fn(<I as ParallelIterator>::Item) -> Option<<I as ParallelIterator>::Item>>;
+
+/// An extension trait for rayon's parallel iterators, to take the integer square roots of their
+/// items across threads. This needs the `rayon` feature.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// extern crate rayon;
+/// use integer_sqrt::ParallelSqrtIterator;
+/// use rayon::prelude::*;
+///
+/// # fn main() {
+/// let roots: Vec<u32> = (0..1000u32).into_par_iter().map_integer_sqrt().collect();
+/// assert_eq!(roots[999], 31);
+/// let roots: Vec<_> = [4i32, -4].par_iter().cloned().map_integer_sqrt_checked().collect();
+/// assert_eq!(roots, [Some(2), None]);
+/// # }
+/// ```
+pub trait ParallelSqrtIterator: ParallelIterator
+where
+    Self::Item: IntegerSquareRoot,
+{
+    /// Find the integer square root of every item.
+    ///
+    /// # Panics
+    ///
+    /// If any item is negative (`i` family)
+    fn map_integer_sqrt(self) -> ParSqrtMap<Self> {
+        self.map(|n| n.integer_sqrt())
+    }
+
+    /// Find the integer square root of every item, giving `None` for the negative ones (this
+    /// can never happen for unsigned types).
+    fn map_integer_sqrt_checked(self) -> ParSqrtCheckedMap<Self> {
+        self.map(|n| n.integer_sqrt_checked())
+    }
+}
+
+impl<I> ParallelSqrtIterator for I
+where
+    I: ParallelIterator,
+    I::Item: IntegerSquareRoot,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+    use std::vec::Vec;
+
+    use super::{par_integer_sqrt_slice, par_integer_sqrt_slice_checked, ParallelSqrtIterator};
+    use IntegerSquareRoot;
+
+    #[test]
+    fn slice() {
+        let values: Vec<u64> = (0..1 << 18).map(|i| i * i * 9_973).collect();
+        let mut roots = values.clone();
+        par_integer_sqrt_slice(&mut roots);
+        for (root, value) in roots.iter().zip(values.iter()) {
+            assert_eq!(*root, value.integer_sqrt(), "in {}", value);
+        }
+        let mut values: Vec<i32> = (0..100_000).collect();
+        values[77_777] = -1;
+        assert!(!par_integer_sqrt_slice_checked(&mut values));
+        assert!(values
+            .iter()
+            .enumerate()
+            .all(|(i, value)| *value == i as i32 || i == 77_777));
+        assert!(par_integer_sqrt_slice_checked(&mut values[..77_777]));
+        assert_eq!(values[77_776], 278);
+    }
+
+    #[test]
+    fn iterator() {
+        let roots: Vec<u16> = (0..=u16::MAX).into_par_iter().map_integer_sqrt().collect();
+        for (n, root) in roots.iter().enumerate() {
+            assert_eq!(*root, (n as u16).integer_sqrt());
+        }
+        let sum: Option<i64> = (-1..100i64)
+            .into_par_iter()
+            .map_integer_sqrt_checked()
+            .sum();
+        assert_eq!(sum, None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative() {
+        par_integer_sqrt_slice(&mut [1i8, -1]);
+    }
+}
@@ -0,0 +1,11 @@
+//! Re-exports of all the traits in this crate, so that they can be imported together with
+//! `use integer_sqrt::prelude::*;`.
+
+pub use {
+    ArraySquareRoot, FigurateRoots, IntegerCubeRoot, IntegerNthRoot, IntegerSqrtAssign,
+    IntegerSqrtIteratorExt, IntegerSquareRoot, MapIntegerSquareRoot, NarrowingSqrt,
+    NonZeroSquareRoot, PerfectSquares, RootsExt, SqrtInto,
+};
+
+#[cfg(feature = "rayon")]
+pub use ParallelSqrtIterator;
@@ -0,0 +1,112 @@
+#[cfg(all(
+    target_arch = "aarch64",
+    target_feature = "neon",
+    not(feature = "portable_simd")
+))]
+use neon;
+#[cfg(all(
+    feature = "portable_simd",
+    not(all(feature = "std", target_arch = "x86_64"))
+))]
+use simd;
+use sqrt_non_negative;
+#[cfg(all(
+    target_arch = "wasm32",
+    target_feature = "simd128",
+    not(feature = "portable_simd")
+))]
+use wasm;
+#[cfg(all(feature = "std", target_arch = "x86_64"))]
+use x86;
+
+/// The primitive integer types, which the traits in this crate are implemented for.
+///
+/// This is what the blanket implementations are bounded on, rather than
+/// `num_traits::PrimInt` itself. Unlike `PrimInt`, no other crate can implement it, so the
+/// compiler knows exactly which types it covers and allows the traits to also be implemented for
+/// types such as `Wrapping<T>`.
+pub trait Primitive: num_traits::PrimInt {
+    /// Replace every element of a slice of non-negative numbers with its integer square root.
+    ///
+    /// The types with a vectorised kernel on the target override this.
+    fn sqrt_slice(values: &mut [Self]) {
+        for value in values.iter_mut() {
+            *value = sqrt_non_negative(*value);
+        }
+    }
+}
+
+macro_rules! impl_primitive {
+    ($($type:ty),*) => {
+        $(
+            impl Primitive for $type {}
+        )*
+    };
+}
+
+impl_primitive!(i8, u8, i16, u16, i32, i64, i128, u128, isize, usize);
+
+#[cfg(not(any(
+    all(target_arch = "aarch64", target_feature = "neon"),
+    all(target_arch = "wasm32", target_feature = "simd128"),
+    all(feature = "std", target_arch = "x86_64"),
+    feature = "portable_simd"
+)))]
+impl_primitive!(u32, u64);
+
+// Slices of the types with a vectorised kernel are rooted with it. The x86-64 kernels are
+// preferred to `portable_simd`, which is preferred to NEON and wasm SIMD.
+macro_rules! impl_primitive_vectorised {
+    ($(#[$attr:meta])* $kernel:ident) => {
+        $(#[$attr])*
+        impl Primitive for u32 {
+            fn sqrt_slice(values: &mut [u32]) {
+                $kernel::sqrt_slice_u32(values)
+            }
+        }
+
+        $(#[$attr])*
+        impl Primitive for u64 {
+            fn sqrt_slice(values: &mut [u64]) {
+                $kernel::sqrt_slice_u64(values)
+            }
+        }
+    };
+}
+
+impl_primitive_vectorised! {
+    #[cfg(all(
+        target_arch = "aarch64",
+        target_feature = "neon",
+        not(feature = "portable_simd")
+    ))]
+    neon
+}
+
+impl_primitive_vectorised! {
+    #[cfg(all(
+        target_arch = "wasm32",
+        target_feature = "simd128",
+        not(feature = "portable_simd")
+    ))]
+    wasm
+}
+
+impl_primitive_vectorised! {
+    #[cfg(all(
+        feature = "portable_simd",
+        not(all(feature = "std", target_arch = "x86_64"))
+    ))]
+    simd
+}
+
+impl_primitive_vectorised! {
+    #[cfg(all(feature = "std", target_arch = "x86_64"))]
+    x86
+}
+
+/// Convert a condition to 1 or 0, to add to or subtract from a root in place of a branch.
+#[inline]
+pub(crate) fn from_bool<T: Primitive>(condition: bool) -> T {
+    T::from(u8::from(condition)).unwrap()
+}
@@ -0,0 +1,267 @@
+use primitive::Primitive;
+use {IntegerCubeRoot, IntegerNthRoot, IntegerSquareRoot};
+
+/// A single trait for square, cube and `k`th roots, each rounded down, up or to nearest, or
+/// taken exactly.
+///
+/// The methods have their own names, so this can be imported on its own or alongside the
+/// fine-grained traits without any ambiguity. Unlike the fine-grained traits, `floor` and `ceil`
+/// really are the floor and ceiling for negative numbers (`i` family), so
+/// `(-28).cbrt_floor()` is `-4` where `(-28).integer_cbrt()` is `-3`.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::RootsExt;
+///
+/// # fn main() {
+/// assert_eq!(30u32.sqrt_ceil(), 6);
+/// assert_eq!(60i32.cbrt_round(), 4);
+/// assert_eq!((-28i32).cbrt_floor(), -4);
+/// assert_eq!(1024u32.root_exact(5), Some(4));
+/// # }
+/// ```
+///
+/// There is no `root_round` for a degree chosen at runtime. Deciding which way to round means
+/// comparing against `(root + 1/2)^k`, which for large degrees cannot be done exactly without
+/// arithmetic wider than the type.
+pub trait RootsExt: IntegerSquareRoot + IntegerCubeRoot + IntegerNthRoot + Sized {
+    /// Find the square root rounded down. See [`IntegerSquareRoot::integer_sqrt`].
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    ///
+    /// [`IntegerSquareRoot::integer_sqrt`]: ./trait.IntegerSquareRoot.html#method.integer_sqrt
+    #[inline]
+    fn sqrt_floor(&self) -> Self {
+        self.integer_sqrt()
+    }
+
+    /// Find the square root rounded up. See [`IntegerSquareRoot::integer_sqrt_ceil`].
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    ///
+    /// [`IntegerSquareRoot::integer_sqrt_ceil`]: ./trait.IntegerSquareRoot.html#method.integer_sqrt_ceil
+    #[inline]
+    fn sqrt_ceil(&self) -> Self {
+        self.integer_sqrt_ceil()
+    }
+
+    /// Find the square root rounded to the nearest integer. See
+    /// [`IntegerSquareRoot::integer_sqrt_round`].
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family) this function will panic on negative input
+    ///
+    /// [`IntegerSquareRoot::integer_sqrt_round`]: ./trait.IntegerSquareRoot.html#method.integer_sqrt_round
+    #[inline]
+    fn sqrt_round(&self) -> Self {
+        self.integer_sqrt_round()
+    }
+
+    /// Find the square root of a perfect square, returning `None` if the number is not a perfect
+    /// square (including when it is negative). See [`IntegerSquareRoot::exact_sqrt`].
+    ///
+    /// [`IntegerSquareRoot::exact_sqrt`]: ./trait.IntegerSquareRoot.html#method.exact_sqrt
+    #[inline]
+    fn sqrt_exact(&self) -> Option<Self> {
+        self.exact_sqrt()
+    }
+
+    /// Find the cube root rounded down, towards negative infinity.
+    fn cbrt_floor(&self) -> Self;
+
+    /// Find the cube root rounded up, towards positive infinity.
+    fn cbrt_ceil(&self) -> Self;
+
+    /// Find the cube root rounded to the nearest integer.
+    ///
+    /// As for square roots, there is never a tie to break.
+    fn cbrt_round(&self) -> Self;
+
+    /// Find the cube root of a perfect cube, returning `None` if the number is not a perfect
+    /// cube.
+    fn cbrt_exact(&self) -> Option<Self>;
+
+    /// Find the `k`th root rounded down, towards negative infinity.
+    ///
+    /// # Panics
+    ///
+    /// If `k` is zero, or for negative numbers (`i` family) when `k` is even.
+    fn root_floor(&self, k: u32) -> Self;
+
+    /// Find the `k`th root rounded up, towards positive infinity.
+    ///
+    /// # Panics
+    ///
+    /// If `k` is zero, or for negative numbers (`i` family) when `k` is even.
+    fn root_ceil(&self, k: u32) -> Self;
+
+    /// Find the `k`th root of a perfect `k`th power, returning `None` if the number is not one
+    /// (including when `k` is zero, or the number is negative and `k` is even).
+    fn root_exact(&self, k: u32) -> Option<Self>;
+}
+
+impl<T: Primitive> RootsExt for T {
+    fn cbrt_floor(&self) -> Self {
+        let root = self.integer_cbrt();
+        floor_from_truncated(*self, root, root * root * root == *self)
+    }
+
+    fn cbrt_ceil(&self) -> Self {
+        let root = self.integer_cbrt();
+        ceil_from_truncated(*self, root, root * root * root == *self)
+    }
+
+    fn cbrt_round(&self) -> Self {
+        // With r = |root| and rem = |self| - r^3, |self| >= (r + 1/2)^3 exactly when
+        // 8 * rem >= 12r^2 + 6r + 1, i.e. (as the right side is odd) when 4 * rem > 3 * r(2r + 1).
+        // floor(3s / 4) is computed as s - ceil(s / 4) so that it cannot overflow.
+        let root = self.integer_cbrt();
+        let cube = root * root * root;
+        let (r, rem) = if *self < T::zero() {
+            (T::zero() - root, cube - *self)
+        } else {
+            (root, *self - cube)
+        };
+        let s = r * (r + r + T::one());
+        let four = T::from(4).unwrap();
+        if rem > s - (s + T::from(3).unwrap()) / four {
+            away_from_zero(*self, root)
+        } else {
+            root
+        }
+    }
+
+    fn cbrt_exact(&self) -> Option<Self> {
+        let root = self.integer_cbrt();
+        if root * root * root == *self {
+            Some(root)
+        } else {
+            None
+        }
+    }
+
+    fn root_floor(&self, k: u32) -> Self {
+        let root = self.nth_root(k);
+        floor_from_truncated(*self, root, is_power_of(root, k, *self))
+    }
+
+    fn root_ceil(&self, k: u32) -> Self {
+        let root = self.nth_root(k);
+        ceil_from_truncated(*self, root, is_power_of(root, k, *self))
+    }
+
+    fn root_exact(&self, k: u32) -> Option<Self> {
+        self.nth_root_checked(k)
+            .filter(|&root| is_power_of(root, k, *self))
+    }
+}
+
+/// Whether `root.pow(k) == n`.
+fn is_power_of<T: Primitive>(root: T, k: u32, n: T) -> bool {
+    num_traits::checked_pow(root, k as usize) == Some(n)
+}
+
+/// Turn a root of `n` rounded towards zero into the floor.
+fn floor_from_truncated<T: Primitive>(n: T, root: T, exact: bool) -> T {
+    if n < T::zero() && !exact {
+        root - T::one()
+    } else {
+        root
+    }
+}
+
+/// Turn a root of `n` rounded towards zero into the ceiling.
+fn ceil_from_truncated<T: Primitive>(n: T, root: T, exact: bool) -> T {
+    if n > T::zero() && !exact {
+        root + T::one()
+    } else {
+        root
+    }
+}
+
+/// Step a root of `n` one further from zero, in the direction of its sign.
+fn away_from_zero<T: Primitive>(n: T, root: T) -> T {
+    if n < T::zero() {
+        root - T::one()
+    } else {
+        root + T::one()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use RootsExt;
+
+    /// The floor of the real `k`th root of `n`, by correcting a floating point estimate.
+    fn reference_floor(n: i16, k: u32) -> i64 {
+        let n = i64::from(n);
+        let estimate = (n.abs() as f64).powf(1.0 / f64::from(k)).round() as i64;
+        let mut root = if n < 0 { -estimate } else { estimate };
+        while root.pow(k) > n {
+            root -= 1;
+        }
+        while (root + 1).pow(k) <= n {
+            root += 1;
+        }
+        root
+    }
+
+    #[test]
+    fn cube_roots() {
+        for n in i16::MIN..=i16::MAX {
+            let floor = reference_floor(n, 3);
+            let exact = floor.pow(3) == i64::from(n);
+            let ceil = if exact { floor } else { floor + 1 };
+            // n >= (floor + 1/2)^3 exactly when 8n >= (2 floor + 1)^3
+            let round = if 8 * i64::from(n) >= (2 * floor + 1).pow(3) {
+                floor + 1
+            } else {
+                floor
+            };
+            assert_eq!(i64::from(n.cbrt_floor()), floor, "in {}", n);
+            assert_eq!(i64::from(n.cbrt_ceil()), ceil, "in {}", n);
+            assert_eq!(i64::from(n.cbrt_round()), round, "in {}", n);
+            assert_eq!(n.cbrt_exact().is_some(), exact, "in {}", n);
+        }
+        for &n in [u8::MAX, 215, 216, 217].iter() {
+            assert_eq!(n.cbrt_round(), (i16::from(n).cbrt_round()) as u8);
+        }
+        assert_eq!(i8::MIN.cbrt_floor(), -6);
+        assert_eq!(i8::MIN.cbrt_round(), -5);
+        assert_eq!(u128::MAX.cbrt_ceil(), u128::MAX.cbrt_floor() + 1);
+    }
+
+    #[test]
+    fn kth_roots() {
+        for k in 1..=7 {
+            for n in i16::MIN..=i16::MAX {
+                if n < 0 && k % 2 == 0 {
+                    assert_eq!(n.root_exact(k), None);
+                    continue;
+                }
+                let floor = reference_floor(n, k);
+                let exact = floor.pow(k) == i64::from(n);
+                let ceil = if exact { floor } else { floor + 1 };
+                assert_eq!(i64::from(n.root_floor(k)), floor, "in {} {}", n, k);
+                assert_eq!(i64::from(n.root_ceil(k)), ceil, "in {} {}", n, k);
+                assert_eq!(n.root_exact(k).is_some(), exact, "in {} {}", n, k);
+            }
+        }
+        assert_eq!(1000u32.root_exact(0), None);
+    }
+
+    #[test]
+    fn square_roots() {
+        assert_eq!(
+            (17u8.sqrt_floor(), 17u8.sqrt_ceil(), 17u8.sqrt_round()),
+            (4, 5, 4)
+        );
+        assert_eq!(16u8.sqrt_exact(), Some(4));
+        assert_eq!((-16i8).sqrt_exact(), None);
+    }
+}
@@ -0,0 +1,206 @@
+use std::simd::cmp::{SimdPartialEq, SimdPartialOrd};
+use std::simd::num::{SimdFloat, SimdUint};
+use std::simd::{Select, Simd, StdFloat};
+
+use {sqrt_non_negative, IntegerSquareRoot, SqrtResult};
+
+// Every lane is estimated with the vector float square root and corrected as in `float_sqrt`,
+// with comparison masks, so there are no branches on the values. The estimate is within 1 of the
+// root and at most 2^(bits / 2), whose square wraps to 0, so that overshoots too. Slices of `u32`
+// and `u64` are rooted with these as well, through `IntegerSqrtAssign`.
+//
+// Methods that return a single answer for the whole vector, like `sqrt_bits` and `exact_sqrt`,
+// answer for all of the lanes together.
+macro_rules! impl_simd {
+    ($($(#[$attr:meta])* $type:ty => $float:ty, $slice:ident, $lanes:expr);*) => {
+        $(
+            $(#[$attr])*
+            impl<const N: usize> IntegerSquareRoot for Simd<$type, N> {
+                #[inline]
+                fn integer_sqrt_checked(&self) -> Option<Self> {
+                    Some(self.integer_sqrt_or_zero())
+                }
+
+                fn integer_sqrt_or_zero(&self) -> Self {
+                    let n = *self;
+                    let (zero, one) = (Simd::splat(0), Simd::splat(1));
+                    let estimate: Self = n.cast::<$float>().sqrt().cast();
+                    let overshoots = (estimate >> (<$type>::BITS / 2) as $type).simd_ne(zero)
+                        | (estimate * estimate).simd_gt(n);
+                    let root = estimate - overshoots.select(one, zero);
+                    let undershoots = (n - root * root).simd_gt(root + root);
+                    root + undershoots.select(one, zero)
+                }
+
+                /// The number of bits in the widest root of the lanes.
+                fn sqrt_bits_checked(&self) -> Option<u32> {
+                    Some((<$type>::BITS - self.reduce_max().leading_zeros()).div_ceil(2))
+                }
+
+                fn sqrt_estimate_checked(&self) -> Option<(Self, Self)> {
+                    let bits = (Simd::splat(<$type>::BITS as $type) - self.leading_zeros().cast()
+                        + Simd::splat(1))
+                        >> 1;
+                    let power = Simd::splat(1) << bits;
+                    Some((power >> 1, power - Simd::splat(1)))
+                }
+
+                /// The vector root is found without branches and costs the same from any
+                /// starting point, so the hint is not used.
+                fn integer_sqrt_with_hint_checked(&self, _hint: Self) -> Option<Self> {
+                    self.integer_sqrt_checked()
+                }
+
+                fn integer_sqrt_ceil_checked(&self) -> Option<Self> {
+                    let (root, rem) = self.integer_sqrt_rem();
+                    let inexact = rem.simd_ne(Simd::splat(0));
+                    Some(root + inexact.select(Simd::splat(1), Simd::splat(0)))
+                }
+
+                fn integer_sqrt_round_checked(&self) -> Option<Self> {
+                    let (root, rem) = self.integer_sqrt_rem();
+                    let rounds_up = rem.simd_gt(root);
+                    Some(root + rounds_up.select(Simd::splat(1), Simd::splat(0)))
+                }
+
+                fn integer_sqrt_rem_checked(&self) -> Option<(Self, Self)> {
+                    let root = self.integer_sqrt_or_zero();
+                    Some((root, *self - root * root))
+                }
+
+                fn abs_integer_sqrt(&self) -> Self {
+                    self.integer_sqrt_or_zero()
+                }
+
+                fn max_sqrt() -> Self {
+                    Simd::splat(<$type>::max_sqrt())
+                }
+
+                /// The floor and ceiling of every lane, and whether every lane is a perfect
+                /// square.
+                fn sqrt_full_checked(&self) -> Option<SqrtResult<Self>> {
+                    let (root, rem) = self.integer_sqrt_rem();
+                    let inexact = rem.simd_ne(Simd::splat(0));
+                    Some(SqrtResult {
+                        floor: root,
+                        ceil: root + inexact.select(Simd::splat(1), Simd::splat(0)),
+                        is_exact: !inexact.any(),
+                    })
+                }
+
+                /// The roots of the lanes, if every lane is a perfect square.
+                fn exact_sqrt(&self) -> Option<Self> {
+                    let (root, rem) = self.integer_sqrt_rem();
+                    if rem.simd_eq(Simd::splat(0)).all() {
+                        Some(root)
+                    } else {
+                        None
+                    }
+                }
+            }
+
+            /// Replace every element of `values` with its integer square root, a vector at a time.
+            pub(crate) fn $slice(values: &mut [$type]) {
+                let (prefix, middle, suffix) = values.as_simd_mut::<$lanes>();
+                for vector in middle.iter_mut() {
+                    *vector = vector.integer_sqrt_or_zero();
+                }
+                for value in prefix.iter_mut().chain(suffix) {
+                    *value = sqrt_non_negative(*value);
+                }
+            }
+        )*
+    };
+}
+
+impl_simd! {
+    /// The integer square roots of the lanes of SIMD vectors, with the nightly `portable_simd`
+    /// feature, so that generic code works on vectors unchanged.
+    ///
+    /// ```
+    /// #![feature(portable_simd)]
+    /// extern crate integer_sqrt;
+    /// use integer_sqrt::IntegerSquareRoot;
+    /// use std::simd::Simd;
+    ///
+    /// # fn main() {
+    /// let squares = Simd::from_array([0u32, 15, 16, u32::MAX]);
+    /// assert_eq!(squares.integer_sqrt().to_array(), [0, 3, 4, 65_535]);
+    /// assert_eq!(squares.integer_sqrt_ceil().to_array(), [0, 4, 4, 65_536]);
+    /// # }
+    /// ```
+    u32 => f32, sqrt_slice_u32, 8;
+    u64 => f64, sqrt_slice_u64, 4
+}
+
+#[cfg(test)]
+mod tests {
+    use std::simd::Simd;
+
+    use IntegerSquareRoot;
+
+    macro_rules! check_lanes {
+        ($n:expr) => {{
+            let n = $n;
+            let (root, rem) = n.integer_sqrt_rem();
+            let (lower, upper) = n.sqrt_estimate();
+            let (ceil, round) = (n.integer_sqrt_ceil(), n.integer_sqrt_round());
+            for (i, n) in n.to_array().iter().enumerate() {
+                assert_eq!(root[i], n.integer_sqrt(), "in {}", n);
+                assert_eq!(rem[i], n.integer_sqrt_rem().1, "in {}", n);
+                assert_eq!((lower[i], upper[i]), n.sqrt_estimate(), "in {}", n);
+                assert_eq!(ceil[i], n.integer_sqrt_ceil(), "in {}", n);
+                assert_eq!(round[i], n.integer_sqrt_round(), "in {}", n);
+            }
+        }};
+    }
+
+    #[test]
+    fn lanewise() {
+        for root in (0..=u32::from(u16::MAX)).step_by(4) {
+            let square = root * root;
+            check_lanes!(Simd::from_array([
+                square,
+                square + 2 * root,
+                square.saturating_sub(1),
+                root
+            ]));
+        }
+        for shift in 0..64 {
+            check_lanes!(Simd::from_array([
+                1u64 << shift,
+                (1 << shift) - 1,
+                u64::MAX >> shift
+            ]));
+        }
+        for root in (0..=u64::from(u32::MAX)).rev().step_by(9_973) {
+            let square = root * root;
+            check_lanes!(Simd::from_array([
+                square,
+                square + 2 * root,
+                square.saturating_sub(1),
+                root
+            ]));
+        }
+    }
+
+    #[test]
+    fn whole_vector() {
+        let n = Simd::from_array([0u32, 1, 4, 9, 16, 25, 36, 49]);
+        assert_eq!(
+            n.exact_sqrt(),
+            Some(Simd::from_array([0, 1, 2, 3, 4, 5, 6, 7]))
+        );
+        assert!(n.sqrt_full().is_exact);
+        assert_eq!(n.sqrt_bits(), 3);
+        let n = Simd::from_array([4u64, 5]);
+        assert_eq!(n.exact_sqrt(), None);
+        assert!(!n.sqrt_full().is_exact);
+        assert_eq!(Simd::<u64, 2>::max_sqrt(), Simd::splat(u64::from(u32::MAX)));
+        // Generic code written against the trait works on vectors unchanged
+        assert_eq!(
+            ::isqrt(Simd::from_array([99u32, 100])),
+            Simd::from_array([9, 10])
+        );
+    }
+}
@@ -0,0 +1,318 @@
+use core::mem::MaybeUninit;
+use core::ptr;
+use core::slice;
+
+use primitive::Primitive;
+use squares::is_perfect_square;
+use IntegerSqrtAssign;
+
+/// Write the integer square root of every element of `src` to the same place in `dst`.
+///
+/// This is the batch entry point that the in-place, SIMD and parallel variants build on. The
+/// elements are copied and then rooted in place, with the vectorised kernels where the target has
+/// them.
+///
+/// The slices should be the same length. This is checked with a debug assertion, and otherwise
+/// only as many elements as the shorter slice holds are rooted.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::integer_sqrt_slice;
+///
+/// # fn main() {
+/// let mut roots = [0u32; 4];
+/// integer_sqrt_slice(&[1, 10, 100, 1000], &mut roots);
+/// assert_eq!(roots, [1, 3, 10, 31]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If any element of `src` is negative (`i` family), in which case `dst` is unchanged
+pub fn integer_sqrt_slice<T: Primitive>(src: &[T], dst: &mut [T]) {
+    assert!(
+        integer_sqrt_slice_checked(src, dst),
+        "cannot calculate square root of negative number"
+    );
+}
+
+/// Write the integer square root of every element of `src` to `dst`, returning `false` and
+/// leaving `dst` unchanged if any element is negative (this can never happen for unsigned types).
+pub fn integer_sqrt_slice_checked<T: Primitive>(src: &[T], dst: &mut [T]) -> bool {
+    debug_assert_eq!(src.len(), dst.len(), "slices of different lengths");
+    let len = src.len().min(dst.len());
+    let (src, dst) = (&src[..len], &mut dst[..len]);
+    if src.iter().any(|value| *value < T::zero()) {
+        return false;
+    }
+    dst.copy_from_slice(src);
+    T::sqrt_slice(dst);
+    true
+}
+
+/// Write the integer square root of every element of `src` to the same place in the
+/// uninitialised buffer `dst`, and return the initialised part of `dst`.
+///
+/// This is [`integer_sqrt_slice`] for output buffers that have not been initialised, which saves
+/// filling a large buffer with zeros only to overwrite them. As there, the slices should be the
+/// same length, and otherwise only as many elements as the shorter slice holds are written and
+/// returned.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::integer_sqrt_slice_uninit;
+/// use std::mem::MaybeUninit;
+///
+/// # fn main() {
+/// let mut buffer = [MaybeUninit::<u32>::uninit(); 4];
+/// let roots = integer_sqrt_slice_uninit(&[1, 10, 100, 1000], &mut buffer);
+/// assert_eq!(roots, [1, 3, 10, 31]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If any element of `src` is negative (`i` family), in which case nothing is written
+///
+/// [`integer_sqrt_slice`]: ./fn.integer_sqrt_slice.html
+pub fn integer_sqrt_slice_uninit<'a, T: Primitive>(
+    src: &[T],
+    dst: &'a mut [MaybeUninit<T>],
+) -> &'a mut [T] {
+    integer_sqrt_slice_uninit_checked(src, dst)
+        .expect("cannot calculate square root of negative number")
+}
+
+/// Write the integer square root of every element of `src` to the uninitialised buffer `dst`,
+/// and return the initialised part, or `None` without writing anything if any element is
+/// negative (this can never happen for unsigned types).
+pub fn integer_sqrt_slice_uninit_checked<'a, T: Primitive>(
+    src: &[T],
+    dst: &'a mut [MaybeUninit<T>],
+) -> Option<&'a mut [T]> {
+    debug_assert_eq!(src.len(), dst.len(), "slices of different lengths");
+    let len = src.len().min(dst.len());
+    let src = &src[..len];
+    if src.iter().any(|value| *value < T::zero()) {
+        return None;
+    }
+    // `MaybeUninit<T>` has the same layout as `T`, and the first `len` elements are initialised
+    // by the copy
+    let dst = unsafe {
+        let dst = dst.as_mut_ptr() as *mut T;
+        ptr::copy_nonoverlapping(src.as_ptr(), dst, len);
+        slice::from_raw_parts_mut(dst, len)
+    };
+    T::sqrt_slice(dst);
+    Some(dst)
+}
+
+/// Replace every element of `values` with its integer square root.
+///
+/// This is [`IntegerSqrtAssign::integer_sqrt_assign`] on a slice, as a free function.
+///
+/// # Panics
+///
+/// If any element is negative (`i` family), in which case no element is changed
+///
+/// [`IntegerSqrtAssign::integer_sqrt_assign`]: ./trait.IntegerSqrtAssign.html#tymethod.integer_sqrt_assign
+pub fn integer_sqrt_slice_in_place<T: Primitive>(values: &mut [T]) {
+    values.integer_sqrt_assign();
+}
+
+/// Replace every element of `values` with its integer square root, returning `false` and
+/// leaving the whole slice unchanged if any element is negative.
+pub fn integer_sqrt_slice_in_place_checked<T: Primitive>(values: &mut [T]) -> bool {
+    values.integer_sqrt_assign_checked()
+}
+
+impl<T: Primitive> IntegerSqrtAssign for [T] {
+    /// Replace every element with its integer square root.
+    ///
+    /// # Panics
+    ///
+    /// If any element is negative (`i` family), in which case no element is changed
+    fn integer_sqrt_assign(&mut self) {
+        assert!(
+            self.integer_sqrt_assign_checked(),
+            "cannot calculate square root of negative number"
+        );
+    }
+
+    /// Replace every element with its integer square root, returning `false` and leaving the
+    /// whole slice unchanged if any element is negative.
+    fn integer_sqrt_assign_checked(&mut self) -> bool {
+        if self.iter().any(|value| *value < T::zero()) {
+            return false;
+        }
+        T::sqrt_slice(self);
+        true
+    }
+}
+
+/// Write whether every element of `values` is a perfect square to the same place in `squares`.
+///
+/// Most numbers are ruled out by their residues modulo 64, 63 and 65, which are checked against
+/// small bitmasks, so a root is only taken for about one in 60 of the numbers that are not
+/// squares. This makes scanning many candidates much cheaper than comparing their
+/// [`exact_sqrt`]. Negative numbers are not squares.
+///
+/// The slices should be the same length. This is checked with a debug assertion, and otherwise
+/// only as many elements as the shorter slice holds are checked.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::is_perfect_square_slice;
+///
+/// # fn main() {
+/// let mut squares = [false; 5];
+/// is_perfect_square_slice(&[-4i64, 0, 24, 25, 1 << 62], &mut squares);
+/// assert_eq!(squares, [false, true, false, true, true]);
+/// # }
+/// ```
+///
+/// [`exact_sqrt`]: ./trait.IntegerSquareRoot.html#tymethod.exact_sqrt
+pub fn is_perfect_square_slice<T: Primitive>(values: &[T], squares: &mut [bool]) {
+    debug_assert_eq!(values.len(), squares.len(), "slices of different lengths");
+    for (square, value) in squares.iter_mut().zip(values) {
+        *square = is_perfect_square(*value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem::MaybeUninit;
+
+    use super::{
+        integer_sqrt_slice, integer_sqrt_slice_checked, integer_sqrt_slice_in_place,
+        integer_sqrt_slice_in_place_checked, integer_sqrt_slice_uninit,
+        integer_sqrt_slice_uninit_checked, is_perfect_square_slice,
+    };
+    use IntegerSqrtAssign;
+    use IntegerSquareRoot;
+
+    macro_rules! check_slice {
+        ($type:ty, $values:expr) => {{
+            let mut values = [0 as $type; 64];
+            for (value, n) in values.iter_mut().zip($values) {
+                *value = n;
+            }
+            // Every length, so that the vectorised kernels also leave a tail of each length
+            for len in 0..values.len() {
+                let mut roots = values;
+                roots[..len].integer_sqrt_assign();
+                for (i, (root, value)) in roots.iter().zip(values.iter()).enumerate() {
+                    let expected = if i < len {
+                        value.integer_sqrt()
+                    } else {
+                        *value
+                    };
+                    assert_eq!(*root, expected, "in {} at {} of {}", value, i, len);
+                }
+            }
+        }};
+    }
+
+    #[test]
+    fn elementwise() {
+        // Squares and their neighbours, where the estimates need correcting
+        for shift in 0..16 {
+            check_slice!(
+                u32,
+                (1u32 << shift..).flat_map(|root| [
+                    root * root - 1,
+                    root * root,
+                    root * (root + 2)
+                ])
+            );
+            check_slice!(
+                u64,
+                (u64::from(u32::MAX >> shift) - 21..).flat_map(|root| [
+                    root * root - 1,
+                    root * root,
+                    root * (root + 2)
+                ])
+            );
+        }
+        check_slice!(u32, (0..).map(|i| u32::MAX - i));
+        check_slice!(u64, (0..).map(|i| u64::MAX - i));
+        check_slice!(u64, (0..).map(|i| (1 << 53) + i));
+        check_slice!(i16, (0..).map(|i| i16::MAX - i));
+        check_slice!(u128, (0..).map(|i| u128::MAX - i));
+    }
+
+    #[test]
+    fn out_of_place() {
+        let src: [u64; 37] = core::array::from_fn(|i| (i as u64) << (i % 64));
+        let mut dst = [0; 37];
+        integer_sqrt_slice(&src, &mut dst);
+        for (root, value) in dst.iter().zip(src.iter()) {
+            assert_eq!(*root, value.integer_sqrt(), "in {}", value);
+        }
+        let mut values = src;
+        integer_sqrt_slice_in_place(&mut values);
+        assert_eq!(values, dst);
+
+        let mut dst = [7i16; 3];
+        assert!(!integer_sqrt_slice_checked(&[4, -4, 4], &mut dst));
+        assert_eq!(dst, [7; 3]);
+        assert!(!integer_sqrt_slice_in_place_checked(&mut [4i16, -4]));
+        assert!(integer_sqrt_slice_checked(&[4, 9, 16], &mut dst));
+        assert_eq!(dst, [2, 3, 4]);
+    }
+
+    #[test]
+    fn uninit() {
+        let src: [u32; 29] = core::array::from_fn(|i| u32::MAX >> i);
+        let mut buffer = [MaybeUninit::uninit(); 29];
+        let roots = integer_sqrt_slice_uninit(&src, &mut buffer);
+        for (root, value) in roots.iter().zip(src.iter()) {
+            assert_eq!(*root, value.integer_sqrt(), "in {}", value);
+        }
+        let mut buffer = [MaybeUninit::uninit(); 2];
+        assert!(integer_sqrt_slice_uninit_checked(&[1i64, -1], &mut buffer).is_none());
+        assert_eq!(
+            integer_sqrt_slice_uninit_checked(&[1i64, 4], &mut buffer),
+            Some(&mut [1, 2][..])
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn different_lengths() {
+        integer_sqrt_slice(&[1u8, 2], &mut [0; 3]);
+    }
+
+    #[test]
+    fn negative() {
+        let mut values = [4i32, 9, -1, 16];
+        assert!(!values[..].integer_sqrt_assign_checked());
+        assert_eq!(values, [4, 9, -1, 16]);
+        assert!(values[..2].integer_sqrt_assign_checked());
+        assert_eq!(values, [2, 3, -1, 16]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn negative_panics() {
+        [1i64, -1][..].integer_sqrt_assign();
+    }
+
+    #[test]
+    fn perfect_squares() {
+        let values: [i32; 8] = [-1, 0, 1, 2, 3, 4, 63, 64];
+        let mut squares = [true; 8];
+        is_perfect_square_slice(&values, &mut squares);
+        assert_eq!(
+            squares,
+            [false, true, true, false, false, true, false, true]
+        );
+        let values: [u32; 1 << 12] = core::array::from_fn(|i| i as u32 * 65_521);
+        let mut squares = [false; 1 << 12];
+        is_perfect_square_slice(&values, &mut squares);
+        for (value, square) in values.iter().zip(squares.iter()) {
+            assert_eq!(*square, value.exact_sqrt().is_some(), "in {}", value);
+        }
+    }
+}
@@ -0,0 +1,847 @@
+use core::iter::FusedIterator;
+use core::ops::{Bound, RangeBounds, RangeInclusive};
+
+use primitive::Primitive;
+use {sqrt_non_negative, IntegerSquareRoot};
+
+/// A trait for finding the perfect squares around a number.
+pub trait PerfectSquares {
+    /// Find the smallest perfect square `>= self`.
+    ///
+    /// For negative numbers (`i` family) this is 0.
+    ///
+    /// # Panics
+    ///
+    /// If the square does not fit in `Self`, e.g. for `u8::max_value()` (whose next square, 256,
+    /// is too large).
+    fn next_perfect_square(&self) -> Self
+    where
+        Self: Sized,
+    {
+        self.next_perfect_square_checked()
+            .expect("next perfect square overflows")
+    }
+
+    /// Find the smallest perfect square `>= self`, returning `None` if it does not fit in `Self`.
+    fn next_perfect_square_checked(&self) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Find the largest perfect square `<= self`.
+    ///
+    /// # Panics
+    ///
+    /// For negative numbers (`i` family), which have no perfect square below them.
+    fn previous_perfect_square(&self) -> Self
+    where
+        Self: Sized,
+    {
+        self.previous_perfect_square_checked()
+            .expect("no perfect square below a negative number")
+    }
+
+    /// Find the largest perfect square `<= self`, returning `None` if the number is negative
+    /// (this can never happen for unsigned types).
+    fn previous_perfect_square_checked(&self) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Find the perfect square closest to `self`.
+    ///
+    /// There is never a tie to break: `self` lies between two consecutive squares `r * r` and
+    /// `(r + 1) * (r + 1)`, which are an odd distance `2 * r + 1` apart, so it cannot be exactly
+    /// halfway between them. For negative numbers (`i` family) the nearest square is 0.
+    ///
+    /// # Panics
+    ///
+    /// If the nearest square does not fit in `Self`, e.g. for 250u8 (whose nearest square is 256).
+    fn nearest_square(&self) -> Self
+    where
+        Self: Sized,
+    {
+        self.nearest_square_checked()
+            .expect("nearest perfect square overflows")
+    }
+
+    /// Find the perfect square closest to `self`, returning `None` if it does not fit in `Self`.
+    fn nearest_square_checked(&self) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Find the distance between `self` and the perfect square closest to it (see
+    /// [`nearest_square`]).
+    ///
+    /// The distance always fits in `Self`, even when the nearest square does not, except for the
+    /// distance from `Self::min_value()` to 0 for the `i` family.
+    ///
+    /// # Panics
+    ///
+    /// For `Self::min_value()` of the `i` family.
+    ///
+    /// [`nearest_square`]: #method.nearest_square
+    fn distance_to_nearest_square(&self) -> Self
+    where
+        Self: Sized,
+    {
+        self.distance_to_nearest_square_checked()
+            .expect("distance to nearest perfect square overflows")
+    }
+
+    /// Find the distance between `self` and the perfect square closest to it, returning `None` if
+    /// it does not fit in `Self`.
+    fn distance_to_nearest_square_checked(&self) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Find the root of the perfect square closest to `self`, if that square is no more than
+    /// `tolerance` away, or `None` if there is no square that close.
+    ///
+    /// This is the test for "almost squares" used by heuristics such as Fermat's factorization
+    /// method. Unlike comparing with [`nearest_square`], it works even when the square does not
+    /// fit in `Self`.
+    ///
+    /// ```
+    /// extern crate integer_sqrt;
+    /// use integer_sqrt::PerfectSquares;
+    ///
+    /// # fn main() {
+    /// assert_eq!(98u32.is_within_of_square(2), Some(10));
+    /// assert_eq!(97u32.is_within_of_square(2), None);
+    /// assert_eq!(255u8.is_within_of_square(1), Some(16));
+    /// # }
+    /// ```
+    ///
+    /// [`nearest_square`]: #method.nearest_square
+    fn is_within_of_square(&self, tolerance: Self) -> Option<Self>
+    where
+        Self: Sized;
+}
+
+impl<T: Primitive> PerfectSquares for T {
+    fn next_perfect_square_checked(&self) -> Option<Self> {
+        match self.integer_sqrt_ceil_checked() {
+            Some(root) => root.checked_mul(&root),
+            None => Some(T::zero()),
+        }
+    }
+
+    fn previous_perfect_square_checked(&self) -> Option<Self> {
+        self.integer_sqrt_checked().map(|root| root * root)
+    }
+
+    fn nearest_square_checked(&self) -> Option<Self> {
+        match self.integer_sqrt_round_checked() {
+            Some(root) => root.checked_mul(&root),
+            None => Some(T::zero()),
+        }
+    }
+
+    fn distance_to_nearest_square_checked(&self) -> Option<Self> {
+        // With n = root^2 + rem, the distance down is rem and the distance up is
+        // 2 * root + 1 - rem, both of which fit in `Self` for any non-negative n.
+        match self.integer_sqrt_rem_checked() {
+            Some((root, rem)) if rem > root => Some(root + root + T::one() - rem),
+            Some((_, rem)) => Some(rem),
+            None => T::zero().checked_sub(self),
+        }
+    }
+
+    fn is_within_of_square(&self, tolerance: Self) -> Option<Self> {
+        let (root, distance) = match self.integer_sqrt_rem_checked() {
+            Some((root, rem)) if rem > root => (root + T::one(), root + root + T::one() - rem),
+            Some((root, rem)) => (root, rem),
+            None => (T::zero(), T::zero().checked_sub(self)?),
+        };
+        if distance <= tolerance {
+            Some(root)
+        } else {
+            None
+        }
+    }
+}
+
+/// The bitmask of the squares modulo `m`, with bit `r` set if some `x * x` is `r` modulo `m`.
+const fn square_residues(m: u32) -> u128 {
+    let mut mask = 0;
+    let mut x = 0;
+    while x < m {
+        mask |= 1 << (x * x % m);
+        x += 1;
+    }
+    mask
+}
+
+// Only 12 in 64, 16 in 63 and 21 in 65 residues are squares, and the moduli are coprime, so
+// together they reject all but about 1.5% of the numbers that are not squares.
+const SQUARES_MOD_64: u128 = square_residues(64);
+const SQUARES_MOD_63: u128 = square_residues(63);
+const SQUARES_MOD_65: u128 = square_residues(65);
+
+/// Whether `n` is a perfect square, checking its residues before taking any root.
+pub(crate) fn is_perfect_square<T: Primitive>(n: T) -> bool {
+    if n < T::zero() {
+        return false;
+    }
+    let low = (n & T::from(63).unwrap()).to_u32().unwrap();
+    // One division by 63 * 65 gives both residues. Types too small to hold it are below it.
+    let rem = match T::from(63 * 65) {
+        Some(m) => n % m,
+        None => n,
+    };
+    let rem = rem.to_u32().unwrap();
+    if SQUARES_MOD_64 & (1 << low) == 0
+        || SQUARES_MOD_63 & (1 << (rem % 63)) == 0
+        || SQUARES_MOD_65 & (1 << (rem % 65)) == 0
+    {
+        return false;
+    }
+    let root = sqrt_non_negative(n);
+    root * root == n
+}
+
+/// Iterate over the perfect squares in a range, as `(root, square)` pairs in increasing order.
+///
+/// # Example
+///
+/// ```
+/// # extern crate integer_sqrt;
+/// use integer_sqrt::squares_in_range;
+///
+/// # fn main() {
+/// let squares: Vec<_> = squares_in_range(10u32..=50).collect();
+/// assert_eq!(squares, [(4, 16), (5, 25), (6, 36), (7, 49)]);
+/// # }
+/// ```
+pub fn squares_in_range<T: Primitive>(range: RangeInclusive<T>) -> SquaresInRange<T> {
+    let (start, end) = range.into_inner();
+    // Roots of squares in the range run from ceil(sqrt(start)) to floor(sqrt(end)) inclusive,
+    // where negative numbers contribute nothing. The floor root is at most half as wide as `T`,
+    // so adding one to it to get an exclusive bound cannot overflow.
+    let first = start.integer_sqrt_ceil_checked().unwrap_or_else(T::zero);
+    let end = match end.integer_sqrt_checked() {
+        Some(root) => root + T::one(),
+        None => T::zero(),
+    };
+    SquaresInRange {
+        next: first,
+        end: if end < first { first } else { end },
+    }
+}
+
+/// Count the perfect squares in a range, in constant time.
+///
+/// This is the [`count_remaining`] of [`squares_in_range`], which always fits in `T`.
+///
+/// # Example
+///
+/// ```
+/// # extern crate integer_sqrt;
+/// use integer_sqrt::count_squares_in;
+///
+/// # fn main() {
+/// assert_eq!(count_squares_in(10u32..=50), 4);
+/// assert_eq!(count_squares_in(0u8..=u8::MAX), 16);
+/// # }
+/// ```
+///
+/// [`count_remaining`]: ./struct.SquaresInRange.html#method.count_remaining
+/// [`squares_in_range`]: ./fn.squares_in_range.html
+pub fn count_squares_in<T: Primitive>(range: RangeInclusive<T>) -> T {
+    squares_in_range(range).count_remaining()
+}
+
+/// Find the range of numbers whose integer square root is `root`, i.e. `root * root` to
+/// `(root + 1) * (root + 1) - 1` inclusive.
+///
+/// The end is clamped to `T::max_value()` when it does not fit, which happens for
+/// [`max_sqrt`]. Returns `None` if `root` is negative, or greater than [`max_sqrt`] (so that no
+/// number in `T` has it as a root).
+///
+/// # Example
+///
+/// ```
+/// # extern crate integer_sqrt;
+/// use integer_sqrt::range_with_sqrt;
+///
+/// # fn main() {
+/// assert_eq!(range_with_sqrt(3u8), Some(9..=15));
+/// assert_eq!(range_with_sqrt(15u8), Some(225..=255));
+/// assert_eq!(range_with_sqrt(16u8), None);
+/// # }
+/// ```
+///
+/// [`max_sqrt`]: ./trait.IntegerSquareRoot.html#tymethod.max_sqrt
+pub fn range_with_sqrt<T: Primitive>(root: T) -> Option<RangeInclusive<T>> {
+    if root < T::zero() {
+        return None;
+    }
+    let start = root.checked_mul(&root)?;
+    // (root + 1)^2 - 1 = root^2 + 2 * root, and 2 * root fits whenever root^2 does
+    let end = start
+        .checked_add(&(root + root))
+        .unwrap_or_else(T::max_value);
+    Some(start..=end)
+}
+
+/// Find the integer square roots of the numbers in a range, which are themselves the range from
+/// the root of its first number to the root of its last.
+///
+/// The range can have any kind of ends. The last number of `start..end` is `end - 1`, so its
+/// roots end at the floor root of `end - 1`, which is one less than the floor root of `end` when
+/// `end` is a square. A range without a start begins at 0, and one without an end runs to
+/// `T::max_value()`.
+///
+/// Negative numbers (`i` family) in the range have no root and are ignored, so this returns
+/// `None` if the range is empty or contains no non-negative numbers.
+///
+/// # Example
+///
+/// ```
+/// # extern crate integer_sqrt;
+/// use integer_sqrt::sqrt_interval;
+///
+/// # fn main() {
+/// assert_eq!(sqrt_interval(10u32..=49), Some(3..=7));
+/// assert_eq!(sqrt_interval(10u32..49), Some(3..=6));
+/// assert_eq!(sqrt_interval(..=16u8), Some(0..=4));
+/// assert_eq!(sqrt_interval(200u8..), Some(14..=15));
+/// assert_eq!(sqrt_interval(-10i32..=3), Some(0..=1));
+/// assert_eq!(sqrt_interval(-10i32..=-3), None);
+/// assert_eq!(sqrt_interval(4u32..4), None);
+/// # }
+/// ```
+pub fn sqrt_interval<T: Primitive, R: RangeBounds<T>>(range: R) -> Option<RangeInclusive<T>> {
+    // The first and last numbers in the range, neither of which exists if an excluded end is at
+    // the edge of `T`
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(&T::one())?,
+        Bound::Unbounded => T::zero(),
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end,
+        Bound::Excluded(&end) => end.checked_sub(&T::one())?,
+        Bound::Unbounded => T::max_value(),
+    };
+    if end < start {
+        return None;
+    }
+    let start = if start < T::zero() { T::zero() } else { start };
+    Some(start.integer_sqrt()..=end.integer_sqrt_checked()?)
+}
+
+/// Mark which numbers from `start` onwards are perfect squares, one bit each in `bitmap`.
+///
+/// Bit `i % 64` of `bitmap[i / 64]` is set if `start + i` is a square, and cleared otherwise, for
+/// the `64 * bitmap.len()` numbers from `start` (those past `T::max_value()` are never squares).
+/// Only the squares are visited, stepping from one root to the next, so after clearing the bitmap
+/// this costs O(number of squares) rather than a root per number.
+///
+/// # Example
+///
+/// ```
+/// # extern crate integer_sqrt;
+/// use integer_sqrt::perfect_square_bitmap;
+///
+/// # fn main() {
+/// let mut bitmap = [0; 2];
+/// perfect_square_bitmap(60u32, &mut bitmap);
+/// // 64, 81, 100 and 121 are 4, 21, 40 and 61 past 60, and 144 and 169 are 20 and 45 past 124
+/// assert_eq!(bitmap, [1 << 4 | 1 << 21 | 1 << 40 | 1 << 61, 1 << 20 | 1 << 45]);
+/// # }
+/// ```
+pub fn perfect_square_bitmap<T: Primitive>(start: T, bitmap: &mut [u64]) {
+    for word in bitmap.iter_mut() {
+        *word = 0;
+    }
+    let bits = bitmap.len().saturating_mul(64);
+    for (_, square) in squares_in_range(start..=T::max_value()) {
+        let offset = if start < T::zero() {
+            // The distance up from a negative start is the square plus -start, which is
+            // -(start + 1) + 1 so that negating the minimum cannot overflow
+            let below_zero = (T::zero() - (start + T::one())).to_usize();
+            square
+                .to_usize()
+                .zip(below_zero)
+                .and_then(|(square, below_zero)| {
+                    below_zero
+                        .checked_add(1)
+                        .and_then(|below_zero| square.checked_add(below_zero))
+                })
+        } else {
+            (square - start).to_usize()
+        };
+        match offset {
+            Some(offset) if offset < bits => bitmap[offset / 64] |= 1 << (offset % 64),
+            _ => break,
+        }
+    }
+}
+
+/// An iterator over the perfect squares in a range, created by [`squares_in_range`].
+///
+/// [`squares_in_range`]: ./fn.squares_in_range.html
+#[derive(Debug, Clone)]
+pub struct SquaresInRange<T> {
+    /// The next root to yield
+    next: T,
+    /// One past the last root to yield
+    end: T,
+}
+
+impl<T: Primitive> SquaresInRange<T> {
+    /// The number of squares left, which unlike a `usize` always fits in `T`.
+    ///
+    /// The iterator is only an `ExactSizeIterator` for the types whose count always fits in a
+    /// `usize`, which are `u8`, `i8`, `u16`, `i16`, `i32`, `usize` and `isize`. This gives the
+    /// count for the others, such as `u32` (whose 65,536 squares outnumber a 16 bit `usize`) and
+    /// the 64 and 128 bit types.
+    ///
+    /// ```
+    /// # extern crate integer_sqrt;
+    /// use integer_sqrt::squares_in_range;
+    ///
+    /// # fn main() {
+    /// assert_eq!(squares_in_range(10u32..=50).count_remaining(), 4);
+    /// assert_eq!(squares_in_range(0..=u128::MAX).count_remaining(), 1 << 64);
+    /// # }
+    /// ```
+    pub fn count_remaining(&self) -> T {
+        self.end - self.next
+    }
+
+    /// Whether there are no squares left.
+    pub fn is_empty(&self) -> bool {
+        self.next == self.end
+    }
+}
+
+impl<T: Primitive> Iterator for SquaresInRange<T> {
+    type Item = (T, T);
+
+    fn next(&mut self) -> Option<(T, T)> {
+        if self.next == self.end {
+            return None;
+        }
+        let root = self.next;
+        self.next = root + T::one();
+        Some((root, root * root))
+    }
+
+    /// The count is exact, unless it does not fit in a `usize` (which is only possible for a
+    /// range of the widest types on a narrow target), in which case there is no upper bound.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.count_remaining().to_usize() {
+            Some(len) => (len, Some(len)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+impl<T: Primitive> DoubleEndedIterator for SquaresInRange<T> {
+    fn next_back(&mut self) -> Option<(T, T)> {
+        if self.next == self.end {
+            return None;
+        }
+        self.end = self.end - T::one();
+        Some((self.end, self.end * self.end))
+    }
+}
+
+impl<T: Primitive> FusedIterator for SquaresInRange<T> {}
+
+// A range of `T` holds at most 2^(bits / 2) squares, which fits in the 16 bits of the narrowest
+// `usize` for these types, and in `usize` itself for `usize` and `isize`.
+macro_rules! impl_exact_size {
+    ($($type:ty),*) => {
+        $(
+            impl ExactSizeIterator for SquaresInRange<$type> {}
+        )*
+    };
+}
+
+impl_exact_size!(u8, i8, u16, i16, i32, usize, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        count_squares_in, is_perfect_square, perfect_square_bitmap, range_with_sqrt, sqrt_interval,
+        squares_in_range, PerfectSquares,
+    };
+    use core::ops::Bound;
+    use IntegerSquareRoot;
+
+    #[test]
+    fn perfect_square() {
+        for n in 0..=u16::MAX {
+            assert_eq!(is_perfect_square(n), n.exact_sqrt().is_some(), "in {}", n);
+        }
+        for n in i8::MIN..=i8::MAX {
+            assert_eq!(is_perfect_square(n), n.exact_sqrt().is_some(), "in {}", n);
+        }
+        for root in (0..1u64 << 12).map(|i| u64::MAX - (i << 40)) {
+            let square = u128::from(root) * u128::from(root);
+            assert!(is_perfect_square(square), "in {}", square);
+            assert!(!is_perfect_square(square - 1), "in {}", square - 1);
+            assert!(!is_perfect_square(square + 1), "in {}", square + 1);
+        }
+        assert!(!is_perfect_square(u64::MAX));
+        assert!(!is_perfect_square(i64::MIN));
+    }
+
+    #[test]
+    fn next_perfect_square() {
+        let tests: [(i32, Option<i32>); 8] = [
+            (i32::MIN, Some(0)),
+            (-1, Some(0)),
+            (0, Some(0)),
+            (1, Some(1)),
+            (2, Some(4)),
+            (46_340 * 46_340, Some(46_340 * 46_340)),
+            (46_340 * 46_340 + 1, None),
+            (i32::MAX, None),
+        ];
+        for &(in_, out) in tests.iter() {
+            assert_eq!(in_.next_perfect_square_checked(), out, "in {}", in_);
+        }
+        assert_eq!(225u8.next_perfect_square(), 225);
+        assert_eq!(226u8.next_perfect_square_checked(), None);
+        assert_eq!(u128::MAX.next_perfect_square_checked(), None);
+        assert_eq!(
+            (u64::MAX as u128 * u64::MAX as u128 - 1).next_perfect_square(),
+            u64::MAX as u128 * u64::MAX as u128
+        );
+    }
+
+    #[test]
+    fn previous_perfect_square() {
+        let tests: [(i32, Option<i32>); 7] = [
+            (-1, None),
+            (0, Some(0)),
+            (1, Some(1)),
+            (3, Some(1)),
+            (4, Some(4)),
+            (99, Some(81)),
+            (i32::MAX, Some(46_340 * 46_340)),
+        ];
+        for &(in_, out) in tests.iter() {
+            assert_eq!(in_.previous_perfect_square_checked(), out, "in {}", in_);
+        }
+        assert_eq!(u8::MAX.previous_perfect_square(), 225);
+        assert_eq!(u64::MAX.previous_perfect_square(), (u32::MAX as u64).pow(2));
+    }
+
+    #[test]
+    fn nearest_square() {
+        let tests: [(i32, Option<i32>, Option<i32>); 10] = [
+            (i32::MIN, Some(0), None),
+            (-5, Some(0), Some(5)),
+            (0, Some(0), Some(0)),
+            (1, Some(1), Some(0)),
+            (2, Some(1), Some(1)),
+            (3, Some(4), Some(1)),
+            (6, Some(4), Some(2)),
+            (7, Some(9), Some(2)),
+            (
+                46_340 * 46_340 + 46_340,
+                Some(46_340 * 46_340),
+                Some(46_340),
+            ),
+            (i32::MAX, None, Some(4_634)),
+        ];
+        for &(in_, square, distance) in tests.iter() {
+            assert_eq!(in_.nearest_square_checked(), square, "in {}", in_);
+            assert_eq!(
+                in_.distance_to_nearest_square_checked(),
+                distance,
+                "in {}",
+                in_
+            );
+        }
+        assert_eq!(240u8.nearest_square(), 225);
+        assert_eq!(241u8.nearest_square_checked(), None);
+        assert_eq!(241u8.distance_to_nearest_square(), 15);
+        assert_eq!(u128::MAX.nearest_square_checked(), None);
+        assert_eq!(u128::MAX.distance_to_nearest_square(), 1);
+    }
+
+    #[test]
+    fn is_within_of_square() {
+        for n in i16::MIN + 1..=i16::MAX {
+            let distance = n.distance_to_nearest_square();
+            for tolerance in -1..=3 {
+                let expected = if distance <= tolerance {
+                    Some(n.integer_sqrt_round_checked().unwrap_or(0))
+                } else {
+                    None
+                };
+                assert_eq!(n.is_within_of_square(tolerance), expected, "in {}", n);
+            }
+        }
+        assert_eq!(i16::MIN.is_within_of_square(i16::MAX), None);
+        assert_eq!(i16::MIN.is_within_of_square(0), None);
+        assert_eq!(u128::MAX.is_within_of_square(1), Some(1 << 64));
+        assert_eq!(u128::MAX.is_within_of_square(0), None);
+    }
+
+    #[test]
+    fn squares_in_range_test() {
+        let collect = |range| {
+            let iter = squares_in_range(range);
+            let (len, remaining) = (iter.len(), iter.count_remaining());
+            let mut squares = [(0, 0); 8];
+            let mut count = 0;
+            for (slot, square) in squares.iter_mut().zip(iter) {
+                *slot = square;
+                count += 1;
+            }
+            assert_eq!(len, count);
+            assert_eq!(remaining, count as i32);
+            (squares, count)
+        };
+        assert_eq!(collect(0i32..=0), ([(0, 0); 8], 1));
+        let (high, low) = (1, 0);
+        assert_eq!(collect(high..=low).1, 0);
+        assert_eq!(collect(-5..=-1).1, 0);
+        assert_eq!(collect(2..=3).1, 0);
+        let (squares, count) = collect(-10..=16);
+        assert_eq!(
+            &squares[..count],
+            &[(0, 0), (1, 1), (2, 4), (3, 9), (4, 16)]
+        );
+        let (squares, count) = collect(17..=36);
+        assert_eq!(&squares[..count], &[(5, 25), (6, 36)]);
+        let (squares, count) = collect(i32::MAX - 200_000..=i32::MAX);
+        assert_eq!(
+            &squares[..count],
+            &[(46_339, 46_339 * 46_339), (46_340, 46_340 * 46_340)]
+        );
+
+        assert_eq!(squares_in_range(0u8..=u8::MAX).len(), 16);
+        assert_eq!(squares_in_range(0u16..=u16::MAX).len(), 256);
+        assert_eq!(
+            squares_in_range(0usize..=usize::MAX).len(),
+            1 << (usize::BITS / 2)
+        );
+        assert_eq!(squares_in_range(0u32..=u32::MAX).count_remaining(), 1 << 16);
+        assert_eq!(squares_in_range(0u64..=u64::MAX).count_remaining(), 1 << 32);
+        assert_eq!(
+            squares_in_range(0u128..=u128::MAX).count_remaining(),
+            1 << 64
+        );
+        assert_eq!(squares_in_range(0u128..=u128::MAX).size_hint().1, None);
+        assert!(squares_in_range(2u8..=3).is_empty());
+        assert_eq!(
+            squares_in_range(0u64..=u64::MAX).next_back(),
+            Some((u32::MAX as u64, (u32::MAX as u64).pow(2)))
+        );
+        assert_eq!(
+            squares_in_range(i128::MIN..=i128::MAX).count_remaining(),
+            13_043_817_825_332_782_213
+        );
+        let mut iter = squares_in_range(1u16..=9);
+        assert_eq!(iter.next_back(), Some((3, 9)));
+        assert_eq!(iter.next(), Some((1, 1)));
+        assert_eq!(iter.next(), Some((2, 4)));
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.next(), None);
+    }
+
+    #[test]
+    fn count_squares_in_test() {
+        // Number of squares <= n, counted by stepping through every n
+        let mut at_most = [0u32; 1 << 16];
+        let mut count = 0;
+        let mut next_root = 0u32;
+        for n in 0..=u16::MAX {
+            if u32::from(n) == next_root * next_root {
+                count += 1;
+                next_root += 1;
+            }
+            at_most[n as usize] = count;
+        }
+        for start in (1..=u16::MAX).step_by(61) {
+            for end in (0..=u16::MAX).step_by(53) {
+                let expected = at_most[end as usize].saturating_sub(at_most[start as usize - 1]);
+                assert_eq!(
+                    u32::from(count_squares_in(start..=end)),
+                    expected,
+                    "{}..={}",
+                    start,
+                    end
+                );
+            }
+        }
+        assert_eq!(count_squares_in(0u8..=0), 1);
+        assert_eq!(count_squares_in(-100i8..=-1), 0);
+        assert_eq!(count_squares_in(i8::MIN..=i8::MAX), 12);
+        assert_eq!(count_squares_in(0u128..=u128::MAX), 1 << 64);
+        assert_eq!(count_squares_in(u128::MAX..=u128::MAX), 0);
+        assert_eq!(count_squares_in(1u32..=u32::MAX), 65535);
+    }
+
+    #[test]
+    fn range_with_sqrt_test() {
+        let mut expected_start = 0;
+        for root in 0..=u16::max_sqrt() {
+            let range = range_with_sqrt(root).unwrap();
+            assert_eq!(*range.start(), expected_start, "root {}", root);
+            assert!(
+                range.clone().all(|n| n.integer_sqrt() == root),
+                "root {}",
+                root
+            );
+            expected_start = range.end().wrapping_add(1);
+        }
+        assert_eq!(expected_start, 0);
+        assert_eq!(range_with_sqrt(u16::max_sqrt() + 1), None);
+        assert_eq!(range_with_sqrt(-1i32), None);
+        assert_eq!(range_with_sqrt(0i32), Some(0..=0));
+        assert_eq!(range_with_sqrt(11i8), Some(121..=127));
+        assert_eq!(range_with_sqrt(12i8), None);
+        assert_eq!(
+            range_with_sqrt(u64::MAX as u128),
+            Some((u64::MAX as u128).pow(2)..=u128::MAX)
+        );
+        assert_eq!(range_with_sqrt(u64::MAX as u128 + 1), None);
+    }
+
+    #[test]
+    fn sqrt_interval_test() {
+        for start in (0..=u16::MAX).step_by(61) {
+            for end in (start..=u16::MAX).step_by(53) {
+                assert_eq!(
+                    sqrt_interval(start..=end),
+                    Some(start.integer_sqrt()..=end.integer_sqrt()),
+                    "{}..={}",
+                    start,
+                    end
+                );
+            }
+        }
+        // The last root of start..end is that of end - 1, which differs when end is a square
+        for end in 1..=u16::MAX {
+            assert_eq!(
+                sqrt_interval(0..end),
+                Some(0..=(end - 1).integer_sqrt()),
+                "0..{}",
+                end
+            );
+            assert_eq!(sqrt_interval(..end), sqrt_interval(0..end), "..{}", end);
+        }
+        for start in 0..u16::MAX {
+            let excluded = (Bound::Excluded(start), Bound::Unbounded);
+            assert_eq!(
+                sqrt_interval(excluded),
+                Some((start + 1).integer_sqrt()..=u16::MAX.integer_sqrt()),
+                "{}..",
+                start
+            );
+        }
+        assert_eq!(sqrt_interval(17u8..25), Some(4..=4));
+        assert_eq!(sqrt_interval(16u8..25), Some(4..=4));
+        assert_eq!(sqrt_interval(16u8..26), Some(4..=5));
+        assert_eq!(sqrt_interval(0u8..0), None);
+        assert_eq!(sqrt_interval(5u8..5), None);
+        assert_eq!(sqrt_interval(i8::MIN..0), None);
+        assert_eq!(sqrt_interval(i8::MIN..1), Some(0..=0));
+        assert_eq!(
+            sqrt_interval((Bound::Excluded(u8::MAX), Bound::Unbounded)),
+            None
+        );
+        assert_eq!(sqrt_interval::<u8, _>(..), Some(0..=15));
+        assert_eq!(sqrt_interval::<i64, _>(..), Some(0..=3_037_000_499));
+        assert_eq!(
+            sqrt_interval(u128::MAX..),
+            Some(u64::MAX as u128..=u64::MAX as u128)
+        );
+        let (high, low) = (5u8, 4);
+        assert_eq!(sqrt_interval(high..=low), None);
+        assert_eq!(sqrt_interval(0u8..=u8::MAX), Some(0..=15));
+        assert_eq!(sqrt_interval(i64::MIN..=-1), None);
+        assert_eq!(sqrt_interval(i64::MIN..=0), Some(0..=0));
+        assert_eq!(sqrt_interval(i64::MIN..=i64::MAX), Some(0..=3_037_000_499));
+        assert_eq!(
+            sqrt_interval(u128::MAX..=u128::MAX),
+            Some(u64::MAX as u128..=u64::MAX as u128)
+        );
+    }
+
+    #[test]
+    fn exhaustive_u16() {
+        // Track the floor root incrementally as an independent oracle
+        let mut root = 0u32;
+        for n in 0..=u16::MAX {
+            while (root + 1) * (root + 1) <= u32::from(n) {
+                root += 1;
+            }
+            let prev = root * root;
+            let next = if prev == u32::from(n) {
+                prev
+            } else {
+                (root + 1) * (root + 1)
+            };
+            let nearest = if u32::from(n) - prev <= next - u32::from(n) {
+                prev
+            } else {
+                next
+            };
+            assert_eq!(u32::from(n.previous_perfect_square()), prev, "in {}", n);
+            assert_eq!(
+                n.nearest_square_checked().map(u32::from),
+                Some(nearest).filter(|&nearest| nearest <= u32::from(u16::MAX)),
+                "in {}",
+                n
+            );
+            assert_eq!(
+                u32::from(n.distance_to_nearest_square()),
+                (i64::from(n) - i64::from(nearest)).unsigned_abs() as u32,
+                "in {}",
+                n
+            );
+            assert_eq!(
+                n.next_perfect_square_checked().map(u32::from),
+                Some(next).filter(|&next| next <= u32::from(u16::MAX)),
+                "in {}",
+                n
+            );
+        }
+    }
+
+    macro_rules! check_bitmap {
+        ($start:expr, $words:expr) => {{
+            let start = $start;
+            let mut bitmap = [u64::MAX; $words];
+            perfect_square_bitmap(start, &mut bitmap);
+            let mut n = Some(start);
+            for i in 0..64 * $words {
+                let is_square = bitmap[i / 64] & 1 << (i % 64) != 0;
+                assert_eq!(is_square, n.map_or(false, is_perfect_square), "in {:?}", n);
+                n = n.and_then(|n| n.checked_add(1));
+            }
+        }};
+    }
+
+    #[test]
+    fn bitmap() {
+        for start in 0..=u8::MAX {
+            check_bitmap!(start, 1);
+            check_bitmap!(start, 3);
+        }
+        for start in i8::MIN..=i8::MAX {
+            check_bitmap!(start, 2);
+            check_bitmap!(start, 5);
+        }
+        for start in (0..1u64 << 12).map(|i| i << 20) {
+            check_bitmap!(start, 4);
+        }
+        check_bitmap!(u64::MAX - 100, 2);
+        check_bitmap!(u128::from(u64::MAX).pow(2) - 200, 8);
+        check_bitmap!(i64::MIN, 1);
+        check_bitmap!(-70i64, 2);
+        // -(start + 1) is usize::MAX on 64 bit targets, so the offset of 0 overflows a usize
+        check_bitmap!(-(1i128 << 64), 1);
+        check_bitmap!(i128::MIN, 1);
+        perfect_square_bitmap(0u32, &mut []);
+    }
+}
@@ -0,0 +1,286 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use newton::newton_sqrt_from;
+use primitive::Primitive;
+
+/// The integer square roots of every `u8`.
+const U8_ROOTS: [u8; 256] = {
+    let mut roots = [0; 256];
+    let mut n = 0;
+    while n < 256 {
+        // The root of n is the root of n - 1, plus one if n is the next square
+        let previous = if n == 0 { 0 } else { roots[n - 1] };
+        let next = previous as usize + 1;
+        roots[n] = if next * next == n {
+            next as u8
+        } else {
+            previous
+        };
+        n += 1;
+    }
+    roots
+};
+
+/// Find the integer square root of non-negative `n`, of at most 8 bits, by looking it up.
+#[inline]
+pub(crate) fn table_sqrt<T: Primitive>(n: T) -> T {
+    T::from(U8_ROOTS[n.to_usize().unwrap()]).unwrap()
+}
+
+/// Find the integer square root of non-negative `n` by Newton's method, starting from the root of
+/// its top byte looked up in the table.
+///
+/// If `n` is `top * 2^shift` plus less than `2^shift` for an even shift and a top byte of at
+/// least 64, and `r` is the root of the top byte, then `sqrt(n) < (r + 1) * 2^(shift / 2)`. That
+/// guess is within an eighth of the root, so it takes only a couple of Newton steps, as in
+/// Hacker's Delight.
+pub(crate) fn table_seeded_sqrt<T: Primitive>(n: T) -> T {
+    let bits = T::zero().leading_zeros() - n.leading_zeros();
+    if bits <= 8 {
+        return table_sqrt(n);
+    }
+    let shift = (bits - 7) & !1;
+    let top = U8_ROOTS[n.unsigned_shr(shift).to_usize().unwrap()];
+    let guess = T::from(top + 1).unwrap().unsigned_shl(shift / 2);
+    newton_sqrt_from(n, guess)
+}
+
+/// The roots of every number below a bound, for answering the same small range of queries many
+/// times over. This needs the `alloc` feature; [`ConstSqrtTable`] is the same without allocating.
+///
+/// Every root is stored, as a `u16`, so building the table costs O(bound) time and two bytes per
+/// number, and each lookup is a single index.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::SqrtTable;
+///
+/// # fn main() {
+/// let table = SqrtTable::new(1000);
+/// assert_eq!(table.integer_sqrt(999u32), 31);
+/// assert_eq!(table.integer_sqrt_checked(1000u32), None);
+/// assert_eq!(table.integer_sqrt_checked(-1i16), None);
+/// # }
+/// ```
+///
+/// [`ConstSqrtTable`]: ./struct.ConstSqrtTable.html
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SqrtTable {
+    roots: Vec<u16>,
+}
+
+#[cfg(feature = "alloc")]
+impl SqrtTable {
+    /// Find the roots of every number below `bound`.
+    ///
+    /// # Panics
+    ///
+    /// If `bound` is more than 2^32, past which the roots no longer fit in a `u16`.
+    pub fn new(bound: usize) -> Self {
+        assert!(bound as u64 <= 1 << 32, "SqrtTable bound is more than 2^32");
+        let mut roots = Vec::with_capacity(bound);
+        // Each root r is the root of the 2r + 1 numbers from r^2
+        let mut root = 0;
+        while roots.len() < bound {
+            let run = (2 * root as usize + 1).min(bound - roots.len());
+            roots.extend((0..run).map(|_| root));
+            root += 1;
+        }
+        SqrtTable { roots }
+    }
+
+    /// The number that every root in the table is below.
+    pub fn bound(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Look up the integer square root of `n`.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is negative (`i` family) or not below the bound of the table.
+    pub fn integer_sqrt<T: Primitive>(&self, n: T) -> T {
+        self.integer_sqrt_checked(n)
+            .expect("number is negative or outside of the SqrtTable")
+    }
+
+    /// Look up the integer square root of `n`, or `None` if it is negative or not below the bound
+    /// of the table.
+    pub fn integer_sqrt_checked<T: Primitive>(&self, n: T) -> Option<T> {
+        lookup(&self.roots, n)
+    }
+}
+
+/// The roots of every number below `N`, built in a constant so that it can be placed in a
+/// `static`, such as in the flash of an embedded target.
+///
+/// Every root is stored, as a `u16`, so the table takes two bytes per number, and each lookup is
+/// a single index.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::ConstSqrtTable;
+///
+/// const TABLE: ConstSqrtTable<1024> = ConstSqrtTable::new();
+/// static ROOTS: ConstSqrtTable<1024> = TABLE;
+///
+/// # fn main() {
+/// assert_eq!(ROOTS.integer_sqrt(1000u16), 31);
+/// assert_eq!(ROOTS.integer_sqrt_checked(1024u16), None);
+/// const ROOT: Option<u16> = TABLE.get(144);
+/// assert_eq!(ROOT, Some(12));
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstSqrtTable<const N: usize> {
+    roots: [u16; N],
+}
+
+impl<const N: usize> ConstSqrtTable<N> {
+    /// Find the roots of every number below `N`.
+    ///
+    /// # Panics
+    ///
+    /// If `N` is more than 2^32, past which the roots no longer fit in a `u16`. This fails to
+    /// compile when the table is built in a constant.
+    pub const fn new() -> Self {
+        assert!(
+            N as u64 <= 1 << 32,
+            "ConstSqrtTable bound is more than 2^32"
+        );
+        let mut roots = [0; N];
+        let mut n = 1;
+        while n < N {
+            // The root of n is the root of n - 1, plus one if n is the next square
+            let next = roots[n - 1] as u64 + 1;
+            roots[n] = if next * next == n as u64 {
+                next as u16
+            } else {
+                roots[n - 1]
+            };
+            n += 1;
+        }
+        ConstSqrtTable { roots }
+    }
+
+    /// The number that every root in the table is below, `N`.
+    pub const fn bound(&self) -> usize {
+        N
+    }
+
+    /// Look up the integer square root of `n`, or `None` if it is not below `N`. Unlike the
+    /// other lookups, this can be used in constants.
+    pub const fn get(&self, n: usize) -> Option<u16> {
+        if n < N {
+            Some(self.roots[n])
+        } else {
+            None
+        }
+    }
+
+    /// Look up the integer square root of `n`.
+    ///
+    /// # Panics
+    ///
+    /// If `n` is negative (`i` family) or not below `N`.
+    pub fn integer_sqrt<T: Primitive>(&self, n: T) -> T {
+        self.integer_sqrt_checked(n)
+            .expect("number is negative or outside of the ConstSqrtTable")
+    }
+
+    /// Look up the integer square root of `n`, or `None` if it is negative or not below `N`.
+    pub fn integer_sqrt_checked<T: Primitive>(&self, n: T) -> Option<T> {
+        lookup(&self.roots, n)
+    }
+}
+
+impl<const N: usize> Default for ConstSqrtTable<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Look up the root of `n` in `roots`. It is at most `n`, so it always fits in `T`.
+fn lookup<T: Primitive>(roots: &[u16], n: T) -> Option<T> {
+    let root = *roots.get(n.to_usize()?)?;
+    T::from(root)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "alloc")]
+    use super::SqrtTable;
+    use super::{table_seeded_sqrt, table_sqrt, ConstSqrtTable};
+    use newton::newton_sqrt;
+    use IntegerSquareRoot;
+
+    #[test]
+    fn table() {
+        for n in 0..=u8::MAX {
+            let root = u16::from(table_sqrt(n));
+            let n = u16::from(n);
+            assert!(root * root <= n && (root + 1) * (root + 1) > n, "in {}", n);
+        }
+        for n in 0..=i8::MAX {
+            assert_eq!(table_sqrt(n) as u8, table_sqrt(n as u8), "in {}", n);
+        }
+    }
+
+    #[test]
+    fn table_seeded() {
+        for n in 0..=u16::MAX {
+            assert_eq!(table_seeded_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for n in (0..=u32::MAX)
+            .step_by(65_537)
+            .chain(u32::MAX - (1 << 16)..=u32::MAX)
+        {
+            assert_eq!(table_seeded_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+        for n in (0..=i32::MAX).rev().step_by(65_521) {
+            assert_eq!(table_seeded_sqrt(n), newton_sqrt(n), "in {}", n);
+        }
+    }
+
+    #[test]
+    fn const_sqrt_table() {
+        static ROOTS: ConstSqrtTable<{ 1 << 16 }> = ConstSqrtTable::new();
+        for n in 0..=u16::MAX {
+            assert_eq!(ROOTS.integer_sqrt(n), n.integer_sqrt(), "in {}", n);
+            assert_eq!(ROOTS.get(n as usize), Some(n.integer_sqrt()), "in {}", n);
+        }
+        assert_eq!(ROOTS.bound(), 1 << 16);
+        assert_eq!(ROOTS.get(1 << 16), None);
+        assert_eq!(ROOTS.integer_sqrt_checked(1u32 << 16), None);
+        assert_eq!(ROOTS.integer_sqrt_checked(-1i32), None);
+        assert_eq!(ROOTS.integer_sqrt(i8::MAX), 11);
+        assert_eq!(ConstSqrtTable::<0>::new().get(0), None);
+        assert_eq!(ConstSqrtTable::<1>::default().get(0), Some(0));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn sqrt_table() {
+        for bound in 0..300 {
+            let table = SqrtTable::new(bound);
+            assert_eq!(table.bound(), bound);
+            for n in 0..bound as u32 {
+                assert_eq!(table.integer_sqrt(n), n.integer_sqrt(), "in {}", n);
+            }
+            assert_eq!(table.integer_sqrt_checked(bound), None);
+        }
+        let table = SqrtTable::new(1 << 20);
+        for n in 0..1u64 << 20 {
+            assert_eq!(table.integer_sqrt(n), n.integer_sqrt(), "in {}", n);
+        }
+        assert_eq!(table.integer_sqrt_checked(-4i64), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn outside_table() {
+        ConstSqrtTable::<16>::new().integer_sqrt(16u8);
+    }
+}
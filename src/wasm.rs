@@ -0,0 +1,60 @@
+use core::arch::wasm32::*;
+
+use sqrt_non_negative;
+
+// The roots are estimated with the vector float square root and corrected as in `float_sqrt`, on
+// whole vectors at once. A true comparison gives a lane of all ones, which is -1, so adding the
+// mask subtracts 1 from the lanes where it holds.
+
+/// Replace every `u32` in `values` with its integer square root, four at a time.
+pub(crate) fn sqrt_slice_u32(values: &mut [u32]) {
+    let mut chunks = values.chunks_exact_mut(4);
+    for chunk in &mut chunks {
+        let n = unsafe { v128_load(chunk.as_ptr() as *const v128) };
+        let estimate = u32x4_trunc_sat_f32x4(f32x4_sqrt(f32x4_convert_u32x4(n)));
+        // The estimate is at most 2^16, whose square wraps to 0, so that overshoots too
+        let overshoots = v128_or(
+            u32x4_gt(estimate, u32x4_splat(0xffff)),
+            u32x4_gt(i32x4_mul(estimate, estimate), n),
+        );
+        let root = i32x4_add(estimate, overshoots);
+        let rem = i32x4_sub(n, i32x4_mul(root, root));
+        let undershoots = u32x4_gt(rem, i32x4_add(root, root));
+        let root = i32x4_sub(root, undershoots);
+        unsafe { v128_store(chunk.as_mut_ptr() as *mut v128, root) };
+    }
+    for value in chunks.into_remainder() {
+        *value = sqrt_non_negative(*value);
+    }
+}
+
+/// Replace every `u64` in `values` with its integer square root, two at a time.
+///
+/// There are no conversions between `f64` and 64 bit integers, nor unsigned 64 bit comparisons,
+/// so the numbers are converted in 32 bit halves and compared with their sign bits flipped. The
+/// roots fit in 32 bits, so they are squared with the widening 32 bit multiply.
+pub(crate) fn sqrt_slice_u64(values: &mut [u64]) {
+    let sign = i64x2_splat(i64::MIN);
+    let gt = |a, b| i64x2_gt(v128_xor(a, sign), v128_xor(b, sign));
+    let mut chunks = values.chunks_exact_mut(2);
+    for chunk in &mut chunks {
+        let n = unsafe { v128_load(chunk.as_ptr() as *const v128) };
+        // Both halves convert exactly, so only their sum is rounded
+        let high = f64x2_convert_low_u32x4(i32x4_shuffle::<1, 3, 1, 3>(n, n));
+        let low = f64x2_convert_low_u32x4(i32x4_shuffle::<0, 2, 0, 2>(n, n));
+        let float = f64x2_add(f64x2_mul(high, f64x2_splat(4_294_967_296.0)), low);
+        // The conversion saturates, so the estimate is at most 2^32 - 1 and never overflows
+        let estimate = u32x4_trunc_sat_f64x2_zero(f64x2_sqrt(float));
+        let overshoots = gt(u64x2_extmul_low_u32x4(estimate, estimate), n);
+        let estimate = u64x2_extend_low_u32x4(estimate);
+        let root = i64x2_add(estimate, overshoots);
+        let narrow = i32x4_shuffle::<0, 2, 0, 2>(root, root);
+        let rem = i64x2_sub(n, u64x2_extmul_low_u32x4(narrow, narrow));
+        let undershoots = gt(rem, i64x2_add(root, root));
+        let root = i64x2_sub(root, undershoots);
+        unsafe { v128_store(chunk.as_mut_ptr() as *mut v128, root) };
+    }
+    for value in chunks.into_remainder() {
+        *value = sqrt_non_negative(*value);
+    }
+}
@@ -0,0 +1,379 @@
+use primitive::Primitive;
+use IntegerSquareRoot;
+
+/// The unsigned primitive integer types, `u8`, `u16`, `u32`, `u64`, `u128` and `usize`, with
+/// arithmetic on values of twice their width.
+///
+/// This is the bound of the functions that compute at double width, such as [`geometric_mean`],
+/// the fixed point roots and [`sqrt_digits`]. It is sealed, so it cannot be implemented outside
+/// this crate.
+///
+/// Double width values are represented as `(high, low)` pairs of halves, which compare correctly
+/// as tuples.
+///
+/// ```
+/// extern crate integer_sqrt;
+/// use integer_sqrt::Widen;
+///
+/// # fn main() {
+/// assert_eq!(u8::MAX.wide_mul(u8::MAX), (254, 1));
+/// assert_eq!(u8::wide_sqrt(254, 1), u8::MAX);
+/// # }
+/// ```
+///
+/// [`geometric_mean`]: ./fn.geometric_mean.html
+/// [`sqrt_digits`]: ./fn.sqrt_digits.html
+pub trait Widen: Primitive + num_traits::Unsigned {
+    /// The full product `self * rhs`, as `(high, low)`.
+    fn wide_mul(self, rhs: Self) -> (Self, Self);
+
+    /// The integer square root of `(high, low)`, which always fits in `Self`.
+    fn wide_sqrt(high: Self, low: Self) -> Self;
+}
+
+macro_rules! impl_widen {
+    ($($type:ty => $wide:ty),*) => {
+        $(
+            impl Widen for $type {
+                #[inline]
+                fn wide_mul(self, rhs: Self) -> (Self, Self) {
+                    let product = self as $wide * rhs as $wide;
+                    ((product >> <$type>::BITS) as $type, product as $type)
+                }
+
+                #[inline]
+                fn wide_sqrt(high: Self, low: Self) -> Self {
+                    (((high as $wide) << <$type>::BITS) | low as $wide).integer_sqrt() as $type
+                }
+            }
+        )*
+    };
+}
+
+impl_widen! {
+    u8 => u16,
+    u16 => u32,
+    u32 => u64,
+    u64 => u128,
+    usize => u128
+}
+
+/// The primitive integer types, with the unsigned type holding their absolute value.
+///
+/// This is the bound of [`sqrt_of_sum_of_squares`], and is implemented for every primitive
+/// integer type. It is sealed, so it cannot be implemented outside this crate.
+///
+/// [`sqrt_of_sum_of_squares`]: ./fn.sqrt_of_sum_of_squares.html
+pub trait Magnitude: Primitive {
+    /// The unsigned type of the same width.
+    type Unsigned: Widen;
+
+    /// The next wider unsigned type, or `u128` for the 128 bit types, which have none.
+    type Wide: Widen;
+
+    /// The absolute value of `self`, which always fits in the unsigned type.
+    fn magnitude(self) -> Self::Unsigned;
+
+    /// The absolute value of `self` as the wide type.
+    fn wide_magnitude(self) -> Self::Wide;
+}
+
+macro_rules! impl_magnitude {
+    ($($type:ty => $unsigned:ty, $wide:ty, $abs:expr);*) => {
+        $(
+            impl Magnitude for $type {
+                type Unsigned = $unsigned;
+                type Wide = $wide;
+
+                #[inline]
+                fn magnitude(self) -> $unsigned {
+                    $abs(self)
+                }
+
+                #[inline]
+                fn wide_magnitude(self) -> $wide {
+                    $abs(self) as $wide
+                }
+            }
+        )*
+    };
+}
+
+impl_magnitude! {
+    u8 => u8, u16, |n| n;
+    u16 => u16, u32, |n| n;
+    u32 => u32, u64, |n| n;
+    u64 => u64, u128, |n| n;
+    u128 => u128, u128, |n| n;
+    usize => usize, u128, |n| n;
+    i8 => u8, u16, i8::unsigned_abs;
+    i16 => u16, u32, i16::unsigned_abs;
+    i32 => u32, u64, i32::unsigned_abs;
+    i64 => u64, u128, i64::unsigned_abs;
+    i128 => u128, u128, i128::unsigned_abs;
+    isize => usize, u128, isize::unsigned_abs
+}
+
+/// The primitive integer types, with an unsigned type that always holds the length of a vector
+/// of up to three of them.
+///
+/// For the `u` family this is the next wider type. For the `i` family the magnitudes are at most
+/// `2^(bits - 1)`, so three squares sum to less than `2^(2 * bits)` and the unsigned type of the
+/// same width is enough. `u128` has no wider type, so it does not implement this.
+///
+/// This is the bound of [`integer_hypot`] and [`integer_hypot3`]. It is sealed, so it cannot be
+/// implemented outside this crate.
+///
+/// [`integer_hypot`]: ./fn.integer_hypot.html
+/// [`integer_hypot3`]: ./fn.integer_hypot3.html
+pub trait Hypot: Magnitude {
+    /// The type holding the lengths.
+    type Length: Widen;
+
+    /// The absolute value of `self` as the length type.
+    fn length(self) -> Self::Length;
+}
+
+macro_rules! impl_hypot {
+    ($($type:ty => $length:ty, $abs:expr);*) => {
+        $(
+            impl Hypot for $type {
+                type Length = $length;
+
+                #[inline]
+                fn length(self) -> $length {
+                    $abs(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_hypot! {
+    u8 => u16, u16::from;
+    u16 => u32, u32::from;
+    u32 => u64, u64::from;
+    u64 => u128, u128::from;
+    usize => u128, |n| n as u128;
+    i8 => u8, i8::unsigned_abs;
+    i16 => u16, i16::unsigned_abs;
+    i32 => u32, i32::unsigned_abs;
+    i64 => u64, i64::unsigned_abs;
+    i128 => u128, i128::unsigned_abs;
+    isize => usize, isize::unsigned_abs
+}
+
+/// The double width sum `a + b`, or `None` if it overflows.
+pub(crate) fn wide_add<T: Widen>(a: (T, T), b: (T, T)) -> Option<(T, T)> {
+    let (high, low) = match a.1.checked_add(&b.1) {
+        Some(low) => (a.0.checked_add(&b.0)?, low),
+        // The low halves carry, and wrap around to `a.1 + b.1 - 2^bits`
+        None => (
+            a.0.checked_add(&b.0)?.checked_add(&T::one())?,
+            a.1 - (T::max_value() - b.1) - T::one(),
+        ),
+    };
+    Some((high, low))
+}
+
+/// The double width product `a * b`, or `None` if it overflows.
+pub(crate) fn wide_mul_narrow<T: Widen>(a: (T, T), b: T) -> Option<(T, T)> {
+    let (carry, low) = a.1.wide_mul(b);
+    let (overflow, high) = a.0.wide_mul(b);
+    if overflow != T::zero() {
+        return None;
+    }
+    Some((high.checked_add(&carry)?, low))
+}
+
+/// `n << shift` at double width, or `None` if it overflows.
+pub(crate) fn wide_shl<T: Widen>(n: T, shift: u32) -> Option<(T, T)> {
+    let bits = T::zero().count_zeros();
+    if n == T::zero() {
+        Some((T::zero(), T::zero()))
+    } else if bits - n.leading_zeros() + shift > 2 * bits {
+        None
+    } else if shift == 0 {
+        Some((T::zero(), n))
+    } else if shift < bits {
+        Some((n.unsigned_shr(bits - shift), n.unsigned_shl(shift)))
+    } else {
+        Some((n.unsigned_shl(shift - bits), T::zero()))
+    }
+}
+
+/// The integer square root of the sum of the squares of `magnitudes`, or `None` if it does not
+/// fit in `T`.
+///
+/// The sum overflows double width exactly when the root would not fit, because the square of the
+/// smallest root which does not fit is `2^(2 * bits)`.
+pub(crate) fn sqrt_sum_of_squares<T, I>(magnitudes: I) -> Option<T>
+where
+    T: Widen,
+    I: IntoIterator<Item = T>,
+{
+    let (high, low) = magnitudes
+        .into_iter()
+        .try_fold((T::zero(), T::zero()), |sum, magnitude| {
+            wide_add(sum, magnitude.wide_mul(magnitude))
+        })?;
+    Some(T::wide_sqrt(high, low))
+}
+
+impl Widen for u128 {
+    fn wide_mul(self, rhs: Self) -> (Self, Self) {
+        // Schoolbook multiplication on 64 bit limbs
+        const MASK: u128 = u64::MAX as u128;
+        let (a_high, a_low) = (self >> 64, self & MASK);
+        let (b_high, b_low) = (rhs >> 64, rhs & MASK);
+        let low = a_low * b_low;
+        let mid = (low >> 64) + ((a_high * b_low) & MASK) + ((a_low * b_high) & MASK);
+        let high =
+            a_high * b_high + ((a_high * b_low) >> 64) + ((a_low * b_high) >> 64) + (mid >> 64);
+        (high, (mid << 64) | (low & MASK))
+    }
+
+    fn wide_sqrt(high: Self, low: Self) -> Self {
+        if high == 0 {
+            return low.integer_sqrt();
+        }
+
+        // Decide the bits of the root one at a time from the top, keeping each bit whose
+        // inclusion leaves `result^2 <= (high, low)`. The root of a number with `bits`
+        // significant bits has at most `bits.div_ceil(2)` bits.
+        let bits = 256 - high.leading_zeros();
+        let mut bit = 1u128 << (bits.div_ceil(2) - 1);
+        let mut result = 0u128;
+        while bit != 0 {
+            let candidate = result | bit;
+            if candidate.wide_mul(candidate) <= (high, low) {
+                result = candidate;
+            }
+            bit >>= 1;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{wide_add, wide_mul_narrow, wide_shl, Widen};
+    use IntegerSquareRoot;
+
+    #[test]
+    fn wide_mul_u128() {
+        let tests: [(u128, u128, (u128, u128)); 5] = [
+            (0, u128::MAX, (0, 0)),
+            (1, u128::MAX, (0, u128::MAX)),
+            (u128::MAX, u128::MAX, (u128::MAX - 1, 1)),
+            (1 << 64, 1 << 64, (1, 0)),
+            (
+                u64::MAX as u128,
+                1 << 127,
+                ((u64::MAX >> 1) as u128, 1 << 127),
+            ),
+        ];
+        for &(a, b, out) in tests.iter() {
+            assert_eq!(a.wide_mul(b), out, "{} * {}", a, b);
+            assert_eq!(b.wide_mul(a), out, "{} * {}", b, a);
+        }
+        for a in (0..1u128 << 14).map(|a| a << 50) {
+            for b in (0..1u128 << 12).map(|b| b << 52) {
+                assert_eq!(a.wide_mul(b), (0, a * b));
+            }
+        }
+    }
+
+    #[test]
+    fn wide_sqrt() {
+        for n in 0..=u16::MAX {
+            let (high, low) = ((n >> 8) as u8, n as u8);
+            assert_eq!(u8::wide_sqrt(high, low), n.integer_sqrt() as u8, "in {}", n);
+        }
+        assert_eq!(u128::wide_sqrt(u128::MAX, u128::MAX), u128::MAX);
+        assert_eq!(u128::wide_sqrt(u128::MAX - 1, 1), u128::MAX);
+        assert_eq!(u128::wide_sqrt(u128::MAX - 1, 0), u128::MAX - 1);
+        assert_eq!(u128::wide_sqrt(1, 0), 1 << 64);
+        assert_eq!(u128::wide_sqrt(0, 99), 9);
+        let mut x = 3u128;
+        while let Some(next) = x.checked_mul(3) {
+            for &r in [x - 1, x, x + 1].iter() {
+                let (high, low) = r.wide_mul(r);
+                assert_eq!(u128::wide_sqrt(high, low), r, "root {}", r);
+                if low > 0 {
+                    assert_eq!(u128::wide_sqrt(high, low - 1), r - 1, "root {}", r);
+                }
+            }
+            x = next;
+        }
+    }
+
+    #[test]
+    fn wide_add_test() {
+        for a in (0..=u16::MAX).step_by(7) {
+            for b in (0..=u16::MAX).step_by(11) {
+                let split = |n: u32| ((n >> 8) as u8, n as u8);
+                let sum = u32::from(a) + u32::from(b);
+                let expected = if sum > u32::from(u16::MAX) {
+                    None
+                } else {
+                    Some(split(sum))
+                };
+                assert_eq!(
+                    wide_add(split(a.into()), split(b.into())),
+                    expected,
+                    "{} + {}",
+                    a,
+                    b
+                );
+            }
+        }
+        assert_eq!(
+            wide_add((0, u128::MAX), (0, u128::MAX)),
+            Some((1, u128::MAX - 1))
+        );
+        assert_eq!(wide_add((u128::MAX, u128::MAX), (0, 1)), None);
+    }
+
+    #[test]
+    fn wide_mul_narrow_test() {
+        let split = |n: u32| ((n >> 8) as u8, n as u8);
+        for a in (0..=u16::MAX).step_by(3) {
+            for b in 0..=u8::MAX {
+                let product = u32::from(a) * u32::from(b);
+                let expected = if product > u32::from(u16::MAX) {
+                    None
+                } else {
+                    Some(split(product))
+                };
+                assert_eq!(
+                    wide_mul_narrow(split(a.into()), b),
+                    expected,
+                    "{} * {}",
+                    a,
+                    b
+                );
+            }
+        }
+        assert_eq!(wide_mul_narrow((1, u128::MAX), 2), Some((3, u128::MAX - 1)));
+        assert_eq!(wide_mul_narrow((1u128 << 127, 0), 2), None);
+    }
+
+    #[test]
+    fn wide_shl_test() {
+        for n in 0..=u8::MAX {
+            for shift in 0..20 {
+                let shifted = u32::from(n) << shift;
+                let expected = if shifted > u32::from(u16::MAX) {
+                    None
+                } else {
+                    Some(((shifted >> 8) as u8, shifted as u8))
+                };
+                assert_eq!(wide_shl(n, shift), expected, "{} << {}", n, shift);
+            }
+        }
+        assert_eq!(wide_shl(1u128, 255), Some((1 << 127, 0)));
+        assert_eq!(wide_shl(2u128, 255), None);
+        assert_eq!(wide_shl(0u128, 1000), Some((0, 0)));
+    }
+}
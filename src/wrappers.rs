@@ -0,0 +1,127 @@
+use core::num::{Saturating, Wrapping};
+
+use {IntegerSquareRoot, SqrtResult};
+
+// The square root of a value never overflows, so wrapping and saturating arithmetic give the same
+// results as plain arithmetic and the implementations just delegate to the inner value.
+macro_rules! impl_wrapper {
+    ($($wrapper:ident),*) => {
+        $(
+            impl<T: IntegerSquareRoot> IntegerSquareRoot for $wrapper<T> {
+                #[inline]
+                fn integer_sqrt_checked(&self) -> Option<Self> {
+                    self.0.integer_sqrt_checked().map($wrapper)
+                }
+
+                #[inline]
+                fn integer_sqrt_or_zero(&self) -> Self {
+                    $wrapper(self.0.integer_sqrt_or_zero())
+                }
+
+                #[inline]
+                fn sqrt_bits_checked(&self) -> Option<u32> {
+                    self.0.sqrt_bits_checked()
+                }
+
+                #[inline]
+                fn sqrt_estimate_checked(&self) -> Option<(Self, Self)> {
+                    self.0
+                        .sqrt_estimate_checked()
+                        .map(|(lower, upper)| ($wrapper(lower), $wrapper(upper)))
+                }
+
+                #[inline]
+                fn integer_sqrt_with_hint_checked(&self, hint: Self) -> Option<Self> {
+                    self.0.integer_sqrt_with_hint_checked(hint.0).map($wrapper)
+                }
+
+                #[inline]
+                fn integer_sqrt_ceil_checked(&self) -> Option<Self> {
+                    self.0.integer_sqrt_ceil_checked().map($wrapper)
+                }
+
+                #[inline]
+                fn integer_sqrt_round_checked(&self) -> Option<Self> {
+                    self.0.integer_sqrt_round_checked().map($wrapper)
+                }
+
+                #[inline]
+                fn integer_sqrt_rem_checked(&self) -> Option<(Self, Self)> {
+                    self.0
+                        .integer_sqrt_rem_checked()
+                        .map(|(root, rem)| ($wrapper(root), $wrapper(rem)))
+                }
+
+                #[inline]
+                fn abs_integer_sqrt(&self) -> Self {
+                    $wrapper(self.0.abs_integer_sqrt())
+                }
+
+                #[inline]
+                fn max_sqrt() -> Self {
+                    $wrapper(T::max_sqrt())
+                }
+
+                #[inline]
+                fn sqrt_full_checked(&self) -> Option<SqrtResult<Self>> {
+                    self.0.sqrt_full_checked().map(|full| SqrtResult {
+                        floor: $wrapper(full.floor),
+                        ceil: $wrapper(full.ceil),
+                        is_exact: full.is_exact,
+                    })
+                }
+
+                #[inline]
+                fn exact_sqrt(&self) -> Option<Self> {
+                    self.0.exact_sqrt().map($wrapper)
+                }
+            }
+        )*
+    };
+}
+
+impl_wrapper!(Wrapping, Saturating);
+
+#[cfg(test)]
+mod tests {
+    use core::num::{Saturating, Wrapping};
+
+    use IntegerSquareRoot;
+
+    #[test]
+    fn wrapping() {
+        for n in 0..=u16::MAX {
+            assert_eq!(Wrapping(n).integer_sqrt(), Wrapping(n.integer_sqrt()));
+            assert_eq!(
+                Wrapping(n).integer_sqrt_ceil(),
+                Wrapping(n.integer_sqrt_ceil())
+            );
+        }
+        assert_eq!(Wrapping(-4i32).integer_sqrt_checked(), None);
+        assert_eq!(Wrapping(-4i32).integer_sqrt_or_zero(), Wrapping(0));
+        assert_eq!(Wrapping(i8::MIN).abs_integer_sqrt(), Wrapping(11));
+        assert_eq!(Wrapping::<u64>::max_sqrt(), Wrapping(u64::max_sqrt()));
+        assert_eq!(
+            Wrapping(10u32).integer_sqrt_rem(),
+            (Wrapping(3), Wrapping(1))
+        );
+        let full = Wrapping(10u32).sqrt_full();
+        assert_eq!((full.floor, full.ceil), (Wrapping(3), Wrapping(4)));
+    }
+
+    #[test]
+    fn saturating() {
+        for n in i16::MIN..=i16::MAX {
+            assert_eq!(
+                Saturating(n).integer_sqrt_checked(),
+                n.integer_sqrt_checked().map(Saturating)
+            );
+            assert_eq!(Saturating(n).exact_sqrt(), n.exact_sqrt().map(Saturating));
+        }
+        assert_eq!(
+            Saturating(u128::MAX).integer_sqrt(),
+            Saturating(u128::MAX.integer_sqrt())
+        );
+        assert_eq!(Saturating(99u8).integer_sqrt_round(), Saturating(10));
+    }
+}
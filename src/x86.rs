@@ -0,0 +1,254 @@
+use core::arch::x86_64::*;
+
+#[cfg(feature = "portable_simd")]
+use simd::{sqrt_slice_u32 as fallback_u32, sqrt_slice_u64 as fallback_u64};
+#[cfg(not(feature = "portable_simd"))]
+use sqrt_non_negative;
+
+// Slices of `u32` and `u64` are rooted with AVX-512 or AVX2 if the CPU has them, which is checked
+// at runtime, so a binary built for the x86-64 baseline still uses them. The AVX-512 kernels need
+// Rust 1.89, and older compilers go straight to AVX2. Each kernel estimates
+// the roots with the vector float square root and corrects them as in `float_sqrt`, with
+// comparison masks. The estimate is within 1 of the root and at most 2^(bits / 2), whose square
+// wraps to 0, so that overshoots too.
+//
+// AVX2 only has signed comparisons, and no conversions between floats and unsigned or 64 bit
+// integers, so its kernels flip the sign bits to compare and convert in halves.
+
+/// Replace every `u32` in `values` with its integer square root, with the widest vectors the CPU
+/// has.
+pub(crate) fn sqrt_slice_u32(values: &mut [u32]) {
+    #[cfg(has_avx512)]
+    {
+        if ::std::is_x86_feature_detected!("avx512f") {
+            return unsafe { sqrt_slice_u32_avx512(values) };
+        }
+    }
+    if ::std::is_x86_feature_detected!("avx2") {
+        unsafe { sqrt_slice_u32_avx2(values) }
+    } else {
+        fallback_u32(values)
+    }
+}
+
+/// Replace every `u64` in `values` with its integer square root, with the widest vectors the CPU
+/// has.
+pub(crate) fn sqrt_slice_u64(values: &mut [u64]) {
+    #[cfg(has_avx512)]
+    {
+        if ::std::is_x86_feature_detected!("avx512f") && ::std::is_x86_feature_detected!("avx512dq")
+        {
+            return unsafe { sqrt_slice_u64_avx512(values) };
+        }
+    }
+    if ::std::is_x86_feature_detected!("avx2") {
+        unsafe { sqrt_slice_u64_avx2(values) }
+    } else {
+        fallback_u64(values)
+    }
+}
+
+/// Root the elements one at a time, for CPUs without AVX2 and the tails of the slices.
+#[cfg(not(feature = "portable_simd"))]
+fn fallback_u32(values: &mut [u32]) {
+    for value in values.iter_mut() {
+        *value = sqrt_non_negative(*value);
+    }
+}
+
+#[cfg(not(feature = "portable_simd"))]
+fn fallback_u64(values: &mut [u64]) {
+    for value in values.iter_mut() {
+        *value = sqrt_non_negative(*value);
+    }
+}
+
+#[cfg(has_avx512)]
+#[allow(clippy::incompatible_msrv)]
+#[target_feature(enable = "avx512f")]
+unsafe fn sqrt_slice_u32_avx512(values: &mut [u32]) {
+    let mut chunks = values.chunks_exact_mut(16);
+    for chunk in &mut chunks {
+        let n = _mm512_loadu_si512(chunk.as_ptr() as *const _);
+        let estimate = _mm512_cvttps_epu32(_mm512_sqrt_ps(_mm512_cvtepu32_ps(n)));
+        let overshoots = _mm512_cmpgt_epu32_mask(estimate, _mm512_set1_epi32(0xffff))
+            | _mm512_cmpgt_epu32_mask(_mm512_mullo_epi32(estimate, estimate), n);
+        let root = _mm512_mask_sub_epi32(estimate, overshoots, estimate, _mm512_set1_epi32(1));
+        let rem = _mm512_sub_epi32(n, _mm512_mullo_epi32(root, root));
+        let undershoots = _mm512_cmpgt_epu32_mask(rem, _mm512_add_epi32(root, root));
+        let root = _mm512_mask_add_epi32(root, undershoots, root, _mm512_set1_epi32(1));
+        _mm512_storeu_si512(chunk.as_mut_ptr() as *mut _, root);
+    }
+    fallback_u32(chunks.into_remainder());
+}
+
+#[cfg(has_avx512)]
+#[allow(clippy::incompatible_msrv)]
+#[target_feature(enable = "avx512f,avx512dq")]
+unsafe fn sqrt_slice_u64_avx512(values: &mut [u64]) {
+    let mut chunks = values.chunks_exact_mut(8);
+    for chunk in &mut chunks {
+        let n = _mm512_loadu_si512(chunk.as_ptr() as *const _);
+        let estimate = _mm512_cvttpd_epu64(_mm512_sqrt_pd(_mm512_cvtepu64_pd(n)));
+        // The roots fit in 32 bits, so they are squared with the widening 32 bit multiply
+        let overshoots = _mm512_cmpgt_epu64_mask(estimate, _mm512_set1_epi64(0xffff_ffff))
+            | _mm512_cmpgt_epu64_mask(_mm512_mul_epu32(estimate, estimate), n);
+        let root = _mm512_mask_sub_epi64(estimate, overshoots, estimate, _mm512_set1_epi64(1));
+        let rem = _mm512_sub_epi64(n, _mm512_mul_epu32(root, root));
+        let undershoots = _mm512_cmpgt_epu64_mask(rem, _mm512_add_epi64(root, root));
+        let root = _mm512_mask_add_epi64(root, undershoots, root, _mm512_set1_epi64(1));
+        _mm512_storeu_si512(chunk.as_mut_ptr() as *mut _, root);
+    }
+    fallback_u64(chunks.into_remainder());
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn sqrt_slice_u32_avx2(values: &mut [u32]) {
+    let mut chunks = values.chunks_exact_mut(8);
+    for chunk in &mut chunks {
+        let n = _mm256_loadu_si256(chunk.as_ptr() as *const _);
+        // Both halves convert exactly, so only their sum is rounded
+        let high = _mm256_cvtepi32_ps(_mm256_srli_epi32(n, 16));
+        let low = _mm256_cvtepi32_ps(_mm256_and_si256(n, _mm256_set1_epi32(0xffff)));
+        let float = _mm256_add_ps(_mm256_mul_ps(high, _mm256_set1_ps(65_536.0)), low);
+        let estimate = _mm256_cvttps_epi32(_mm256_sqrt_ps(float));
+        // Comparisons give all ones, which is -1, where they hold
+        let overshoots = _mm256_or_si256(
+            _mm256_cmpgt_epi32(estimate, _mm256_set1_epi32(0xffff)),
+            gt_epu32(_mm256_mullo_epi32(estimate, estimate), n),
+        );
+        let root = _mm256_add_epi32(estimate, overshoots);
+        let rem = _mm256_sub_epi32(n, _mm256_mullo_epi32(root, root));
+        let undershoots = gt_epu32(rem, _mm256_add_epi32(root, root));
+        let root = _mm256_sub_epi32(root, undershoots);
+        _mm256_storeu_si256(chunk.as_mut_ptr() as *mut _, root);
+    }
+    fallback_u32(chunks.into_remainder());
+}
+
+#[target_feature(enable = "avx2")]
+unsafe fn sqrt_slice_u64_avx2(values: &mut [u64]) {
+    // Placing 32 bits in the mantissa of 2^52 or 2^84 gives their exact value, plus that power
+    let (exp_52, exp_84) = (0x4330_0000_0000_0000, 0x4530_0000_0000_0000);
+    let (power_52, power_84) = (f64::from_bits(exp_52 as u64), f64::from_bits(exp_84 as u64));
+    let mut chunks = values.chunks_exact_mut(4);
+    for chunk in &mut chunks {
+        let n = _mm256_loadu_si256(chunk.as_ptr() as *const _);
+        let high = _mm256_or_si256(_mm256_srli_epi64(n, 32), _mm256_set1_epi64x(exp_84));
+        let low = _mm256_blend_epi32(_mm256_set1_epi64x(exp_52), n, 0b0101_0101);
+        // The difference is exact, and the sum is the only rounding
+        let high = _mm256_sub_pd(
+            _mm256_castsi256_pd(high),
+            _mm256_set1_pd(power_84 + power_52),
+        );
+        let float = _mm256_add_pd(high, _mm256_castsi256_pd(low));
+        // Adding 2^52 leaves the root, rounded to the nearest integer, in the mantissa
+        let rounded = _mm256_add_pd(_mm256_sqrt_pd(float), _mm256_set1_pd(power_52));
+        let estimate = _mm256_xor_si256(_mm256_castpd_si256(rounded), _mm256_set1_epi64x(exp_52));
+        let overshoots = _mm256_or_si256(
+            _mm256_cmpgt_epi64(estimate, _mm256_set1_epi64x(0xffff_ffff)),
+            gt_epu64(_mm256_mul_epu32(estimate, estimate), n),
+        );
+        let root = _mm256_add_epi64(estimate, overshoots);
+        let rem = _mm256_sub_epi64(n, _mm256_mul_epu32(root, root));
+        let undershoots = gt_epu64(rem, _mm256_add_epi64(root, root));
+        let root = _mm256_sub_epi64(root, undershoots);
+        _mm256_storeu_si256(chunk.as_mut_ptr() as *mut _, root);
+    }
+    fallback_u64(chunks.into_remainder());
+}
+
+/// Compare unsigned 32 bit lanes, by flipping their sign bits for the signed comparison.
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn gt_epu32(a: __m256i, b: __m256i) -> __m256i {
+    let sign = _mm256_set1_epi32(i32::MIN);
+    _mm256_cmpgt_epi32(_mm256_xor_si256(a, sign), _mm256_xor_si256(b, sign))
+}
+
+/// Compare unsigned 64 bit lanes, by flipping their sign bits for the signed comparison.
+#[target_feature(enable = "avx2")]
+#[inline]
+unsafe fn gt_epu64(a: __m256i, b: __m256i) -> __m256i {
+    let sign = _mm256_set1_epi64x(i64::MIN);
+    _mm256_cmpgt_epi64(_mm256_xor_si256(a, sign), _mm256_xor_si256(b, sign))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sqrt_slice_u32_avx2, sqrt_slice_u64_avx2};
+    #[cfg(has_avx512)]
+    use super::{sqrt_slice_u32_avx512, sqrt_slice_u64_avx512};
+    use IntegerSquareRoot;
+
+    macro_rules! check_kernel {
+        ($kernel:ident, $type:ty, $values:expr) => {{
+            let mut values = [0 as $type; 67];
+            for (value, n) in values.iter_mut().zip($values) {
+                *value = n;
+            }
+            let mut roots = values;
+            unsafe { $kernel(&mut roots) };
+            for (root, value) in roots.iter().zip(values.iter()) {
+                assert_eq!(*root, value.integer_sqrt(), "in {}", value);
+            }
+        }};
+    }
+
+    macro_rules! check_kernels {
+        ($kernel_u32:ident, $kernel_u64:ident) => {{
+            for shift in 0..16 {
+                check_kernel!(
+                    $kernel_u32,
+                    u32,
+                    (1u32 << shift..).flat_map(|root| [
+                        root * root - 1,
+                        root * root,
+                        root * (root + 2)
+                    ])
+                );
+                check_kernel!(
+                    $kernel_u64,
+                    u64,
+                    (u64::from(u32::MAX >> shift) - 23..).flat_map(|root| [
+                        root * root - 1,
+                        root * root,
+                        root * (root + 2)
+                    ])
+                );
+            }
+            for shift in 0..32 {
+                check_kernel!(
+                    $kernel_u64,
+                    u64,
+                    (1u64 << shift..).flat_map(|root| [
+                        root * root - 1,
+                        root * root,
+                        root * (root + 2)
+                    ])
+                );
+            }
+            check_kernel!($kernel_u32, u32, (0..).map(|i| u32::MAX - i));
+            check_kernel!($kernel_u64, u64, (0..).map(|i| u64::MAX - i));
+            check_kernel!($kernel_u64, u64, (0..).map(|i| (1 << 53) + i));
+            check_kernel!($kernel_u32, u32, 0..);
+            check_kernel!($kernel_u64, u64, 0..);
+        }};
+    }
+
+    #[test]
+    fn avx2() {
+        if ::std::is_x86_feature_detected!("avx2") {
+            check_kernels!(sqrt_slice_u32_avx2, sqrt_slice_u64_avx2);
+        }
+    }
+
+    #[test]
+    #[cfg(has_avx512)]
+    fn avx512() {
+        if ::std::is_x86_feature_detected!("avx512f") && ::std::is_x86_feature_detected!("avx512dq")
+        {
+            check_kernels!(sqrt_slice_u32_avx512, sqrt_slice_u64_avx512);
+        }
+    }
+}